@@ -1,4 +1,3 @@
-use rb62;
 use std::str;
 
 fn main() {
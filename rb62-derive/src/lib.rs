@@ -0,0 +1,270 @@
+//! `#[derive(Base62)]` for newtypes over the u128 ids of rb62, e.g. `struct PlaylistId(u128)`.
+//!
+//! Generates `FromStr`, `Display` and `Debug`, and optionally serde impls. Options go in
+//! a `#[rb62(...)]` attribute:
+//!
+//! * `alphabet = "base62"`: `base62` (the default), `base58`, `base36` or `crockford32`
+//! * `uri_prefix = "spotify:playlist:"`: `FromStr` also accepts the prefixed form, and
+//!   `{:#}` displays it
+//! * `display = "hex"`: use the 32 char lowercase hex form instead of `alphabet`
+//! * `serde = "string"` or `serde = "integer"`: implement `Serialize` and `Deserialize`,
+//!   as the displayed string or as a u128. Needs the `serde` feature of rb62.
+//!
+//! ```ignore
+//! #[derive(Base62, Clone, Copy, PartialEq, Eq)]
+//! #[rb62(uri_prefix = "spotify:playlist:", serde = "string")]
+//! struct PlaylistId(u128);
+//! ```
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Type};
+
+#[proc_macro_derive(Base62, attributes(rb62))]
+pub fn derive_base62(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Alphabet {
+    Base62,
+    Base58,
+    Base36,
+    Crockford32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Display {
+    Alphabet,
+    Hex,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Serde {
+    String,
+    Integer,
+}
+
+#[derive(Default)]
+struct Options {
+    alphabet: Option<Alphabet>,
+    uri_prefix: Option<LitStr>,
+    display: Option<Display>,
+    serde: Option<Serde>,
+}
+
+fn parse_options(input: &DeriveInput) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("rb62")) {
+        attr.parse_nested_meta(|meta| {
+            let value: LitStr = meta.value()?.parse()?;
+            let duplicate = || meta.error("duplicate rb62 attribute");
+            if meta.path.is_ident("alphabet") {
+                let alphabet = match value.value().as_str() {
+                    "base62" => Alphabet::Base62,
+                    "base58" => Alphabet::Base58,
+                    "base36" => Alphabet::Base36,
+                    "crockford32" => Alphabet::Crockford32,
+                    _ => return Err(syn::Error::new(
+                        value.span(),
+                        "unknown alphabet, expected one of base62, base58, base36, crockford32",
+                    )),
+                };
+                if options.alphabet.replace(alphabet).is_some() {
+                    return Err(duplicate());
+                }
+            } else if meta.path.is_ident("uri_prefix") {
+                if value.value().is_empty() {
+                    return Err(syn::Error::new(value.span(), "uri_prefix can not be empty"));
+                }
+                if options.uri_prefix.replace(value).is_some() {
+                    return Err(duplicate());
+                }
+            } else if meta.path.is_ident("display") {
+                let display = match value.value().as_str() {
+                    "b62" => Display::Alphabet,
+                    "hex" => Display::Hex,
+                    _ => return Err(syn::Error::new(value.span(), "unknown display, expected b62 or hex")),
+                };
+                if options.display.replace(display).is_some() {
+                    return Err(duplicate());
+                }
+            } else if meta.path.is_ident("serde") {
+                let serde = match value.value().as_str() {
+                    "string" => Serde::String,
+                    "integer" => Serde::Integer,
+                    _ => return Err(syn::Error::new(value.span(), "unknown serde, expected string or integer")),
+                };
+                if options.serde.replace(serde).is_some() {
+                    return Err(duplicate());
+                }
+            } else {
+                return Err(meta.error("unknown rb62 attribute, expected alphabet, uri_prefix, display or serde"));
+            }
+            Ok(())
+        })?;
+    }
+
+    if options.display == Some(Display::Hex) && options.alphabet.is_some() {
+        return Err(syn::Error::new_spanned(&input.ident, "alphabet can not be used with display = \"hex\""));
+    }
+    Ok(options)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let field = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed.first(),
+            _ => None,
+        },
+        _ => None,
+    };
+    let field = field.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "Base62 can only be derived for tuple structs with a single u128 field, like `struct Id(u128);`",
+        )
+    })?;
+    let is_u128 = match &field.ty {
+        Type::Path(ty) => ty.qself.is_none() && matches!(ty.path.segments.last(), Some(s) if s.ident == "u128"),
+        _ => false,
+    };
+    if !is_u128 {
+        return Err(syn::Error::new_spanned(&field.ty, "the field must be a u128"));
+    }
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "Base62 can not be derived for generic structs"));
+    }
+
+    let options = parse_options(input)?;
+    let name = &input.ident;
+    let name_str = name.to_string();
+
+    // `encoded` is a &str of the value `value`
+    let (decode, encode) = match (options.display.unwrap_or(Display::Alphabet), options.alphabet) {
+        (Display::Hex, _) => (
+            quote!(::rb62::hex_format::HexFormat::new().parse(body)),
+            quote! {
+                let mut buf = [0u8; ::rb62::hex_format::MAX_HEX_LEN];
+                let encoded = ::rb62::hex_format::HexFormat::new().format(value, &mut buf);
+            },
+        ),
+        (Display::Alphabet, alphabet) => {
+            let radix = match alphabet.unwrap_or(Alphabet::Base62) {
+                Alphabet::Base62 => quote!(::rb62::radix::BASE62),
+                Alphabet::Base58 => quote!(::rb62::radix::BASE58),
+                Alphabet::Base36 => quote!(::rb62::radix::BASE36),
+                Alphabet::Crockford32 => quote!(::rb62::radix::CROCKFORD32),
+            };
+            (
+                quote!(#radix.decode(body)),
+                quote! {
+                    let buf = #radix.encode(value);
+                    // all alphabets are ASCII
+                    let encoded = ::core::str::from_utf8(&buf).unwrap();
+                },
+            )
+        }
+    };
+
+    let strip_prefix = match &options.uri_prefix {
+        Some(prefix) => quote!(let body = body.strip_prefix(#prefix).unwrap_or(body);),
+        None => quote!(),
+    };
+    let write_prefix = match &options.uri_prefix {
+        Some(prefix) => quote! {
+            if f.alternate() {
+                f.write_str(#prefix)?;
+            }
+        },
+        None => quote!(),
+    };
+
+    let serde = match options.serde {
+        None => quote!(),
+        Some(repr) => {
+            let (serialize, deserialize) = match repr {
+                Serde::String => (
+                    quote! {
+                        let value = self.0;
+                        #encode
+                        serializer.serialize_str(encoded)
+                    },
+                    quote! {
+                        struct Visitor;
+
+                        impl<'de> serde::de::Visitor<'de> for Visitor {
+                            type Value = u128;
+
+                            fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                                write!(f, "a {} string", #name_str)
+                            }
+
+                            fn visit_str<E: serde::de::Error>(self, body: &str) -> ::core::result::Result<u128, E> {
+                                #strip_prefix
+                                #decode.ok_or_else(|| E::custom(::rb62::id::ParseIdError::new(#name_str)))
+                            }
+                        }
+
+                        let value = deserializer.deserialize_str(Visitor)?;
+                    },
+                ),
+                Serde::Integer => (
+                    quote!(serializer.serialize_u128(self.0)),
+                    quote!(let value = <u128 as serde::Deserialize>::deserialize(deserializer)?;),
+                ),
+            };
+            quote! {
+                const _: () = {
+                    use ::rb62::__private::serde;
+
+                    impl serde::Serialize for #name {
+                        fn serialize<S: serde::Serializer>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+                            #serialize
+                        }
+                    }
+
+                    impl<'de> serde::Deserialize<'de> for #name {
+                        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<#name, D::Error> {
+                            #deserialize
+                            ::core::result::Result::Ok(#name(value))
+                        }
+                    }
+                };
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl ::core::str::FromStr for #name {
+            type Err = ::rb62::id::ParseIdError;
+
+            fn from_str(body: &str) -> ::core::result::Result<Self, Self::Err> {
+                #strip_prefix
+                let value: ::core::option::Option<u128> = #decode;
+                value.map(#name).ok_or(::rb62::id::ParseIdError::new(#name_str))
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let value: u128 = self.0;
+                #encode
+                #write_prefix
+                f.write_str(encoded)
+            }
+        }
+
+        impl ::core::fmt::Debug for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, "{}({})", #name_str, self)
+            }
+        }
+
+        #serde
+    })
+}
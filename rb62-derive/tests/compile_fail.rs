@@ -0,0 +1,5 @@
+#[test]
+fn derive_base62_misuse_fails_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
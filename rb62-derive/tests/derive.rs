@@ -0,0 +1,66 @@
+use rb62_derive::Base62;
+
+#[derive(Base62, Clone, Copy, PartialEq, Eq)]
+struct TrackId(u128);
+
+#[derive(Base62, Clone, Copy, PartialEq, Eq)]
+#[rb62(uri_prefix = "spotify:playlist:", serde = "string")]
+struct PlaylistId(u128);
+
+#[derive(Base62, Clone, Copy, PartialEq, Eq)]
+#[rb62(alphabet = "base58", serde = "integer")]
+struct LinkId(u128);
+
+#[derive(Base62, Clone, Copy, PartialEq, Eq)]
+#[rb62(alphabet = "crockford32")]
+struct SupportCode(u128);
+
+#[derive(Base62, Clone, Copy, PartialEq, Eq)]
+#[rb62(display = "hex", serde = "string")]
+struct Gid(u128);
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+#[test]
+fn derive_base62_works() {
+    let id: TrackId = "6GGODyP2LIdbxIfYxy5UbN".parse().expect("id can be parsed");
+    assert_eq!(id.0, VALUE);
+    assert_eq!(id.to_string(), "6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(format!("{:?}", id), "TrackId(6GGODyP2LIdbxIfYxy5UbN)");
+    let err = "6GGODyP2LIdbxIfYxy5Ub".parse::<TrackId>().err().unwrap();
+    assert_eq!(err.to_string(), "invalid TrackId id");
+}
+
+#[test]
+fn derive_uri_prefix_works() {
+    let id: PlaylistId = "spotify:playlist:6GGODyP2LIdbxIfYxy5UbN".parse().unwrap();
+    assert!(id == PlaylistId(VALUE));
+    assert!("6GGODyP2LIdbxIfYxy5UbN".parse::<PlaylistId>().unwrap() == id);
+    assert_eq!(id.to_string(), "6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(format!("{:#}", id), "spotify:playlist:6GGODyP2LIdbxIfYxy5UbN");
+    assert!("spotify:track:6GGODyP2LIdbxIfYxy5UbN".parse::<PlaylistId>().is_err());
+}
+
+#[test]
+fn derive_alphabets_work() {
+    assert_eq!(LinkId(VALUE).to_string(), "U8ycDbEa8WMR5kynpiJriE");
+    assert!("U8ycDbEa8WMR5kynpiJriE".parse::<LinkId>().unwrap() == LinkId(VALUE));
+    assert_eq!(SupportCode(VALUE).to_string(), "6VRFAYQRT4916T7RJ4GW9A08GK");
+    assert!("6vrfayqrt49i6t7rj4gw9ao8gk".parse::<SupportCode>().unwrap() == SupportCode(VALUE));
+    assert_eq!(Gid(VALUE).to_string(), "dbc3d5ebe344484da3e2448712a02213");
+    assert!("dbc3d5ebe344484da3e2448712a02213".parse::<Gid>().unwrap() == Gid(VALUE));
+}
+
+#[test]
+fn derive_serde_works() {
+    assert_eq!(serde_json::to_string(&PlaylistId(VALUE)).unwrap(), r#""6GGODyP2LIdbxIfYxy5UbN""#);
+    assert!(serde_json::from_str::<PlaylistId>(r#""spotify:playlist:6GGODyP2LIdbxIfYxy5UbN""#).unwrap() == PlaylistId(VALUE));
+    let err = serde_json::from_str::<PlaylistId>(r#""nope""#).err().unwrap();
+    assert!(err.to_string().contains("invalid PlaylistId id"));
+
+    assert_eq!(serde_json::to_string(&Gid(VALUE)).unwrap(), r#""dbc3d5ebe344484da3e2448712a02213""#);
+    assert!(serde_json::from_str::<Gid>(r#""dbc3d5ebe344484da3e2448712a02213""#).unwrap() == Gid(VALUE));
+
+    assert_eq!(serde_json::to_string(&LinkId(42)).unwrap(), "42");
+    assert!(serde_json::from_str::<LinkId>("42").unwrap() == LinkId(42));
+}
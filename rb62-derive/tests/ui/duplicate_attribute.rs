@@ -0,0 +1,8 @@
+use rb62_derive::Base62;
+
+#[derive(Base62)]
+#[rb62(serde = "string")]
+#[rb62(serde = "integer")]
+struct Id(u128);
+
+fn main() {}
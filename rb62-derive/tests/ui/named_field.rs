@@ -0,0 +1,8 @@
+use rb62_derive::Base62;
+
+#[derive(Base62)]
+struct Id {
+    value: u128,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use rb62_derive::Base62;
+
+#[derive(Base62)]
+#[rb62(alphabet = "base64")]
+struct Id(u128);
+
+fn main() {}
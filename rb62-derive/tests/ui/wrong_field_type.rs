@@ -0,0 +1,6 @@
+use rb62_derive::Base62;
+
+#[derive(Base62)]
+struct Id(u64);
+
+fn main() {}
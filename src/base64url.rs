@@ -0,0 +1,99 @@
+//! Transcoding between b62 and unpadded base64url (RFC 4648 section 5) for 16 byte ids.
+//!
+//! Both write 16 bytes as 22 chars and share most of their alphabet, so one can easily
+//! be mistaken for the other. `classify` tells which of the two a string can be.
+//! Bytes are taken in big endian order, the same as `get_hex`.
+use core::fmt;
+
+use crate::{get_b62_from_integer, get_integer};
+
+pub(crate) const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// What a 22 char string can be decoded as
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Classification {
+    /// Neither valid b62 nor valid base64url
+    Invalid,
+    Base62(u128),
+    Base64Url(u128),
+    /// Valid as both, with the value for each reading
+    Ambiguous { base62: u128, base64url: u128 },
+}
+
+/// Describes the classification, meant for error messages when rejecting input
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Classification::Invalid => write!(f, "neither valid b62 nor valid base64url"),
+            Classification::Base62(value) => write!(f, "b62 of {:032x}", value),
+            Classification::Base64Url(value) => write!(f, "base64url of {:032x}", value),
+            Classification::Ambiguous { base62, base64url } => write!(
+                f,
+                "ambiguous, b62 of {:032x} or base64url of {:032x}",
+                base62, base64url
+            ),
+        }
+    }
+}
+
+pub fn classify(encoded: &str) -> Classification {
+    match (get_integer(encoded), get_integer_from_base64url(encoded)) {
+        (Some(base62), Some(base64url)) => Classification::Ambiguous { base62, base64url },
+        (Some(base62), None) => Classification::Base62(base62),
+        (None, Some(base64url)) => Classification::Base64Url(base64url),
+        (None, None) => Classification::Invalid,
+    }
+}
+
+/// Turn a u128 into 22 chars of unpadded base64url
+pub fn get_base64url_from_integer(value: u128) -> [u8; 22] {
+    let mut base64url_array = [0u8; 22];
+    // 22 chars hold 132 bits, the last 4 are always 0
+    for (i, c) in base64url_array.iter_mut().enumerate() {
+        let shift = 122 - 6 * i as i32;
+        let six_bits = if shift >= 0 { value >> shift } else { value << -shift };
+        *c = BASE64URL[(six_bits & 0x3f) as usize];
+    }
+    base64url_array
+}
+
+/// Parse 22 chars of unpadded base64url. Only the canonical form is accepted, i.e.
+/// the 4 unused bits of the last char must be 0.
+pub fn get_integer_from_base64url(base64url: &str) -> Option<u128> {
+    let base64url = base64url.as_bytes();
+    if base64url.len() != 22 {
+        return None;
+    }
+
+    let mut bi = 0u128;
+    for c in &base64url[..21] {
+        bi = bi << 6 | base64url_val(*c)? as u128;
+    }
+    let last = base64url_val(base64url[21])?;
+    if last & 0x0f != 0 {
+        return None;
+    }
+    Some(bi << 2 | (last >> 4) as u128)
+}
+
+/// Turn b62 String to unpadded base64url
+pub fn get_base64url(base62: &str) -> Option<[u8; 22]> {
+    Some(get_base64url_from_integer(get_integer(base62)?))
+}
+
+/// Turn unpadded base64url to b62
+pub fn get_b62_from_base64url(base64url: &str) -> Option<[u8; 22]> {
+    Some(get_b62_from_integer(get_integer_from_base64url(base64url)?))
+}
+
+// Returns 0-63
+fn base64url_val(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'-' => Some(62),
+        b'_' => Some(63),
+        _ => None,
+    }
+}
@@ -0,0 +1,412 @@
+//! Compact serialization of sorted id sets, like catalog snapshots, where even the 16
+//! bytes per id of `id_list` are too many.
+//!
+//! Ids are split into blocks of a fixed number of ids. The first id of each block goes
+//! into a block index, the others are stored as the LEB128 varint of the difference to
+//! the id before them. Random ids in a dense set have small differences, so most take
+//! far fewer than 16 bytes. The layout, all fixed width integers big endian:
+//!
+//! | size | field |
+//! |------|-------|
+//! | 4 | magic, `RB6S` |
+//! | 1 | version, `1` |
+//! | 3 | reserved, zero |
+//! | 4 | ids per block, at least 1 |
+//! | ... | the blocks, each the varint differences of all but the first id of the block |
+//! | 24 * blocks | the block index, for each block the first id (16) and the offset of the block in the set (8) |
+//! | 8 | count of ids |
+//! | 8 | count of blocks |
+//!
+//! The index is at the end so a set can be written in one pass.
+//!
+//! ```
+//! use rb62::compact_set::CompactSet;
+//!
+//! # #[cfg(feature = "std")] {
+//! let mut encoder = rb62::compact_set::Encoder::new(Vec::new(), 2).unwrap();
+//! for id in &[1, 5, 6, 0xdbc3d5ebe344484da3e2448712a02213] {
+//!     encoder.push(*id).unwrap();
+//! }
+//! let bytes = encoder.finish().unwrap();
+//!
+//! let set = CompactSet::parse(&bytes).unwrap();
+//! assert_eq!(set.len(), 4);
+//! assert!(set.contains(6));
+//! assert!(!set.contains(7));
+//! # }
+//! ```
+use core::convert::TryInto;
+use core::fmt;
+
+pub const MAGIC: [u8; 4] = *b"RB6S";
+pub const VERSION: u8 = 1;
+pub const HEADER_LEN: usize = 12;
+pub const INDEX_ENTRY_LEN: usize = 24;
+pub const FOOTER_LEN: usize = 16;
+/// Ids per block when there is no reason to pick another size
+pub const DEFAULT_BLOCK_LEN: u32 = 128;
+/// Max length of the varint of a u128
+pub const MAX_VARINT_LEN: usize = 19;
+
+/// Write `value` as a LEB128 varint, returns the number of bytes used
+pub fn write_varint(mut value: u128, buf: &mut [u8; MAX_VARINT_LEN]) -> usize {
+    let mut i = 0;
+    while value >= 0x80 {
+        buf[i] = value as u8 | 0x80;
+        value >>= 7;
+        i += 1;
+    }
+    buf[i] = value as u8;
+    i + 1
+}
+
+/// Read a LEB128 varint from the start of `bytes`, returns the value and the number
+/// of bytes used. Overlong and overflowing varints are rejected.
+pub fn read_varint(bytes: &[u8]) -> Option<(u128, usize)> {
+    let mut value = 0u128;
+    for (i, b) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        let bits = (*b & 0x7f) as u128;
+        if i == MAX_VARINT_LEN - 1 && bits > 0x03 {
+            return None;
+        }
+        value |= bits << (7 * i);
+        if b & 0x80 == 0 {
+            if *b == 0 && i > 0 {
+                return None;
+            }
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompactSetError {
+    BadMagic,
+    UnsupportedVersion(u8),
+    /// Non zero reserved bytes or a block size of zero
+    BadHeader,
+    /// The footer, index and blocks do not fit together
+    Corrupt,
+    /// Ids were not given in strictly ascending order
+    NotSorted,
+}
+
+impl fmt::Display for CompactSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactSetError::BadMagic => write!(f, "not a compact id set"),
+            CompactSetError::UnsupportedVersion(version) => write!(f, "unsupported compact id set version {}", version),
+            CompactSetError::BadHeader => write!(f, "invalid compact id set header"),
+            CompactSetError::Corrupt => write!(f, "corrupt compact id set"),
+            CompactSetError::NotSorted => write!(f, "ids of a compact id set must be strictly ascending"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompactSetError {}
+
+#[cfg(feature = "std")]
+impl From<CompactSetError> for std::io::Error {
+    fn from(err: CompactSetError) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// A validated compact set borrowed from its bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompactSet<'a> {
+    bytes: &'a [u8],
+    block_len: u32,
+    count: u64,
+    index_start: usize,
+    block_count: usize,
+}
+
+impl<'a> CompactSet<'a> {
+    /// Check the header, footer and index, and decode every block once so that
+    /// lookups and iteration can not fail later
+    pub fn parse(bytes: &'a [u8]) -> Result<CompactSet<'a>, CompactSetError> {
+        if bytes.len() < HEADER_LEN + FOOTER_LEN || bytes[..4] != MAGIC {
+            return Err(CompactSetError::BadMagic);
+        }
+        if bytes[4] != VERSION {
+            return Err(CompactSetError::UnsupportedVersion(bytes[4]));
+        }
+        let block_len = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        if bytes[5..8] != [0, 0, 0] || block_len == 0 {
+            return Err(CompactSetError::BadHeader);
+        }
+
+        let footer = &bytes[bytes.len() - FOOTER_LEN..];
+        let count = u64::from_be_bytes(footer[..8].try_into().unwrap());
+        let block_count = u64::from_be_bytes(footer[8..].try_into().unwrap());
+        if block_count != count / block_len as u64 + (count % block_len as u64 != 0) as u64 {
+            return Err(CompactSetError::Corrupt);
+        }
+        let index_len = (block_count as usize)
+            .checked_mul(INDEX_ENTRY_LEN)
+            .filter(|len| *len <= bytes.len() - HEADER_LEN - FOOTER_LEN)
+            .ok_or(CompactSetError::Corrupt)?;
+
+        let set = CompactSet {
+            bytes,
+            block_len,
+            count,
+            index_start: bytes.len() - FOOTER_LEN - index_len,
+            block_count: block_count as usize,
+        };
+        let mut offset = HEADER_LEN;
+        let mut last = None;
+        for block in 0..set.block_count {
+            let (first, block_offset) = set.index_entry(block);
+            let end = set.block_end(block);
+            if block_offset != offset || end < offset || matches!(last, Some(last) if first <= last) {
+                return Err(CompactSetError::Corrupt);
+            }
+            let mut ids = BlockIter::new(first, &bytes[offset..end], set.ids_in_block(block));
+            for id in &mut ids {
+                last = Some(id.ok_or(CompactSetError::Corrupt)?);
+            }
+            if !ids.data.is_empty() {
+                return Err(CompactSetError::Corrupt);
+            }
+            offset = end;
+        }
+        if offset != set.index_start {
+            return Err(CompactSetError::Corrupt);
+        }
+        Ok(set)
+    }
+
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn block_len(&self) -> u32 {
+        self.block_len
+    }
+
+    /// Binary search the block index, then decode the one block which could hold `id`
+    pub fn contains(&self, id: u128) -> bool {
+        let (mut low, mut high) = (0, self.block_count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.index_entry(mid).0 <= id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == 0 {
+            return false;
+        }
+        self.block(low - 1).map(Option::unwrap).take_while(|x| *x <= id).any(|x| x == id)
+    }
+
+    pub fn iter(&self) -> Iter<'a> {
+        Iter { set: *self, block: 0, ids: BlockIter::new(0, &[], 0) }
+    }
+
+    fn index_entry(&self, block: usize) -> (u128, usize) {
+        let entry = &self.bytes[self.index_start + block * INDEX_ENTRY_LEN..][..INDEX_ENTRY_LEN];
+        let first = u128::from_be_bytes(entry[..16].try_into().unwrap());
+        let offset = u64::from_be_bytes(entry[16..].try_into().unwrap());
+        (first, offset.min(usize::MAX as u64) as usize)
+    }
+
+    fn block_end(&self, block: usize) -> usize {
+        if block + 1 == self.block_count {
+            self.index_start
+        } else {
+            self.index_entry(block + 1).1.clamp(HEADER_LEN, self.index_start)
+        }
+    }
+
+    fn ids_in_block(&self, block: usize) -> u64 {
+        (self.count - block as u64 * self.block_len as u64).min(self.block_len as u64)
+    }
+
+    fn block(&self, block: usize) -> BlockIter<'a> {
+        let (first, offset) = self.index_entry(block);
+        BlockIter::new(first, &self.bytes[offset..self.block_end(block)], self.ids_in_block(block))
+    }
+}
+
+impl<'a> IntoIterator for CompactSet<'a> {
+    type Item = u128;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Decodes one block, yields `None` for a corrupt varint or a non ascending id
+struct BlockIter<'a> {
+    next: Option<u128>,
+    data: &'a [u8],
+    remaining: u64,
+}
+
+impl<'a> BlockIter<'a> {
+    fn new(first: u128, data: &'a [u8], count: u64) -> BlockIter<'a> {
+        BlockIter { next: Some(first), data, remaining: count }
+    }
+}
+
+impl Iterator for BlockIter<'_> {
+    type Item = Option<u128>;
+
+    fn next(&mut self) -> Option<Option<u128>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let id = self.next;
+        if self.remaining > 0 {
+            self.next = match (id, read_varint(self.data)) {
+                (Some(id), Some((delta, len))) if delta > 0 => {
+                    self.data = &self.data[len..];
+                    id.checked_add(delta)
+                }
+                _ => None,
+            };
+        }
+        Some(id)
+    }
+}
+
+/// Iterator over the ids of a `CompactSet`, in ascending order
+pub struct Iter<'a> {
+    set: CompactSet<'a>,
+    block: usize,
+    ids: BlockIter<'a>,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        loop {
+            if let Some(id) = self.ids.next() {
+                // blocks were validated by `CompactSet::parse`
+                return id;
+            }
+            if self.block == self.set.block_count {
+                return None;
+            }
+            self.ids = self.set.block(self.block);
+            self.block += 1;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::io::{from_text, to_text, Encoder};
+
+#[cfg(feature = "std")]
+mod io {
+    use std::format;
+    use std::io::{self, BufRead, Write};
+    use std::vec::Vec;
+
+    use super::{write_varint, CompactSet, CompactSetError, HEADER_LEN, MAGIC, MAX_VARINT_LEN, VERSION};
+    use crate::{get_b62_from_integer, get_integer};
+
+    /// Streams a set to any `Write`, ids have to be pushed in strictly ascending order.
+    /// Only the block index is kept in memory.
+    pub struct Encoder<W: Write> {
+        out: W,
+        block_len: u32,
+        offset: u64,
+        index: Vec<(u128, u64)>,
+        count: u64,
+        last: u128,
+    }
+
+    impl<W: Write> Encoder<W> {
+        /// Write the header, `block_len` ids go into each block
+        pub fn new(mut out: W, block_len: u32) -> io::Result<Encoder<W>> {
+            if block_len == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "block length must be at least 1"));
+            }
+            let mut header = [0u8; HEADER_LEN];
+            header[..4].copy_from_slice(&MAGIC);
+            header[4] = VERSION;
+            header[8..].copy_from_slice(&block_len.to_be_bytes());
+            out.write_all(&header)?;
+            Ok(Encoder { out, block_len, offset: HEADER_LEN as u64, index: Vec::new(), count: 0, last: 0 })
+        }
+
+        pub fn push(&mut self, id: u128) -> io::Result<()> {
+            if self.count > 0 && id <= self.last {
+                return Err(CompactSetError::NotSorted.into());
+            }
+            // a new block starts once all blocks so far are full
+            if self.count == self.index.len() as u64 * self.block_len as u64 {
+                self.index.push((id, self.offset));
+            } else {
+                let mut buf = [0u8; MAX_VARINT_LEN];
+                let len = write_varint(id - self.last, &mut buf);
+                self.out.write_all(&buf[..len])?;
+                self.offset += len as u64;
+            }
+            self.last = id;
+            self.count += 1;
+            Ok(())
+        }
+
+        /// Write the block index and footer and hand back the output
+        pub fn finish(mut self) -> io::Result<W> {
+            for (first, offset) in &self.index {
+                self.out.write_all(&first.to_be_bytes())?;
+                self.out.write_all(&offset.to_be_bytes())?;
+            }
+            self.out.write_all(&self.count.to_be_bytes())?;
+            self.out.write_all(&(self.index.len() as u64).to_be_bytes())?;
+            self.out.flush()?;
+            Ok(self.out)
+        }
+    }
+
+    /// Convert b62 text, one id per line, into a set. The ids are sorted and duplicates
+    /// removed, blank lines are ignored. Returns the number of ids in the set.
+    pub fn from_text<R: BufRead, W: Write>(input: R, output: W, block_len: u32) -> io::Result<u64> {
+        let mut ids = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let id = get_integer(line).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("line {} is not a valid id", i + 1))
+            })?;
+            ids.push(id);
+        }
+        ids.sort_unstable();
+        ids.dedup();
+
+        let mut encoder = Encoder::new(output, block_len)?;
+        for id in &ids {
+            encoder.push(*id)?;
+        }
+        encoder.finish()?;
+        Ok(ids.len() as u64)
+    }
+
+    /// Write a set as b62 text, one id per line
+    pub fn to_text<W: Write>(set: &CompactSet<'_>, mut output: W) -> io::Result<()> {
+        for id in set.iter() {
+            output.write_all(&get_b62_from_integer(id))?;
+            output.write_all(b"\n")?;
+        }
+        output.flush()
+    }
+}
@@ -0,0 +1,146 @@
+//! Constant time versions of the b62 and hex conversions, for values that are secrets
+//! (reset tokens, capability urls, ...).
+//!
+//! The normal functions take shortcuts which depend on the value they work on: chars
+//! are looked up in a table indexed by the char, the check against the max value stops
+//! at the first differing digit, and `get_b62` stops dividing once the value hits zero.
+//! The functions here avoid all of that:
+//!
+//! * the time taken depends only on the length of the input, never on its content
+//! * no branches and no table lookups are done on secret data, chars are mapped to
+//!   values and back with masks, and dividing by 62 uses u64 division by a constant
+//!   which compilers turn into a multiply and shift
+//! * decoding always looks at every char and only reveals whether the whole input
+//!   was valid, at the very end
+//!
+//! These are best effort guarantees at the source level, like any constant time Rust
+//! code they rely on the compiler not turning the masks back into branches. The
+//! `ct` integration tests contain a dudect style timing check which can be run locally
+//! with `cargo test --release --test ct -- --ignored`.
+
+// Digits of "7N42dgm5tFLK9N8MT7fHC7", the b62 of u128::MAX
+const MAX_VAL_ARRAY: [u8; 22] = [
+    7, 49, 4, 2, 13, 16, 22, 5, 29, 41, 47, 46, 9, 49, 8, 48, 55, 7, 15, 43, 38, 7,
+];
+
+/// Constant time version of `get_integer`
+pub fn get_integer(base62: &str) -> Option<u128> {
+    let base62 = base62.as_bytes();
+    if base62.len() != 22 {
+        return None;
+    }
+
+    let mut bi = 0u128;
+    let mut invalid = 0u32; // all ones once any char is invalid
+    let mut greater = 0u32; // all ones once the input is known to be above the max value
+    let mut decided = 0u32; // all ones once the input is known to differ from the max value
+    for (c, max_val) in base62.iter().zip(MAX_VAL_ARRAY.iter()) {
+        let (val, valid) = base62_val(*c);
+        invalid |= !valid;
+        greater |= !decided & lt_mask(*max_val as u32, val);
+        decided |= !eq_mask(val, *max_val as u32);
+        bi = bi.wrapping_mul(62).wrapping_add(val as u128);
+    }
+
+    if invalid | greater == 0 {
+        Some(bi)
+    } else {
+        None
+    }
+}
+
+/// Constant time version of `get_b62_from_integer`
+pub fn get_b62_from_integer(value: u128) -> [u8; 22] {
+    let mut b62_val_array = [0u8; 22];
+    let mut limbs = [(value >> 96) as u32, (value >> 64) as u32, (value >> 32) as u32, value as u32];
+    for c in b62_val_array.iter_mut().rev() {
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut() {
+            let current = remainder << 32 | *limb as u64;
+            *limb = (current / 62) as u32;
+            remainder = current % 62;
+        }
+        *c = base62_char(remainder as u32);
+    }
+    b62_val_array
+}
+
+/// Constant time version of `get_b62`, unlike `get_b62` the hex has to be exactly 32 chars
+pub fn get_b62(hex: &str) -> Option<[u8; 22]> {
+    let hex = hex.as_bytes();
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut value = 0u128;
+    let mut invalid = 0u32;
+    for c in hex {
+        let (val, valid) = hex_val(*c);
+        invalid |= !valid;
+        value = value << 4 | val as u128;
+    }
+    if invalid == 0 {
+        Some(get_b62_from_integer(value))
+    } else {
+        None
+    }
+}
+
+/// Constant time version of `get_hex`
+pub fn get_hex(base62: &str) -> Option<[u8; 32]> {
+    let value = get_integer(base62)?;
+    let mut hex_val_array = [0u8; 32];
+    for (i, c) in hex_val_array.iter_mut().enumerate() {
+        let nibble = (value >> (124 - 4 * i)) as u32 & 0x0f;
+        // '0'..'9' then 'a'..'f', 'a' is 39 after '9' + 1
+        *c = (b'0' as u32 + nibble + (lt_mask(9, nibble) & 39)) as u8;
+    }
+    Some(hex_val_array)
+}
+
+// all ones if a < b, for a and b below 2^31
+fn lt_mask(a: u32, b: u32) -> u32 {
+    0u32.wrapping_sub(a.wrapping_sub(b) >> 31)
+}
+
+fn eq_mask(a: u32, b: u32) -> u32 {
+    !(lt_mask(a, b) | lt_mask(b, a))
+}
+
+// all ones if lo <= c <= hi
+fn range_mask(c: u32, lo: u8, hi: u8) -> u32 {
+    !lt_mask(c, lo as u32) & !lt_mask(hi as u32, c)
+}
+
+// Returns the 0-61 value and an all ones mask if the char is valid
+fn base62_val(c: u8) -> (u32, u32) {
+    let c = c as u32;
+    let digit = range_mask(c, b'0', b'9');
+    let lower = range_mask(c, b'a', b'z');
+    let upper = range_mask(c, b'A', b'Z');
+    let val = (digit & c.wrapping_sub(b'0' as u32))
+        | (lower & c.wrapping_sub(b'a' as u32 - 10))
+        | (upper & c.wrapping_sub(b'A' as u32 - 36));
+    (val, digit | lower | upper)
+}
+
+// Returns a char within "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
+fn base62_char(value: u32) -> u8 {
+    let lower = lt_mask(9, value);
+    let upper = lt_mask(35, value);
+    let c = (!lower & (b'0' as u32 + value))
+        | (lower & !upper & ((b'a' as u32 - 10) + value))
+        | (upper & ((b'A' as u32 - 36) + value));
+    c as u8
+}
+
+// Returns the 0-15 value and an all ones mask if the char is a hex digit of either case
+fn hex_val(c: u8) -> (u32, u32) {
+    let c = c as u32;
+    let digit = range_mask(c, b'0', b'9');
+    let lower = range_mask(c, b'a', b'f');
+    let upper = range_mask(c, b'A', b'F');
+    let val = (digit & c.wrapping_sub(b'0' as u32))
+        | (lower & c.wrapping_sub(b'a' as u32 - 10))
+        | (upper & c.wrapping_sub(b'A' as u32 - 10));
+    (val, digit | lower | upper)
+}
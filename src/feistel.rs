@@ -0,0 +1,101 @@
+//! Keyed, reversible scrambling of u128 ids, so sequential internal keys can be
+//! shown publicly without being enumerable.
+//!
+//! `Feistel` is a balanced Feistel network over the low `bits` bits of a u128. Every
+//! Feistel network is a bijection no matter the round function, so `deobfuscate`
+//! always gets the original id back. The bits above `bits` are passed through as is,
+//! which makes the whole thing a permutation of u128 as well as of `0..2^bits`.
+use crate::{get_b62_from_integer, get_integer};
+
+/// Default number of rounds, enough for ids to look random
+pub const DEFAULT_ROUNDS: u32 = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Feistel {
+    key: u128,
+    rounds: u32,
+    half_bits: u32,
+}
+
+impl Feistel {
+    /// Scramble all 128 bits with `DEFAULT_ROUNDS` rounds
+    pub const fn new(key: u128) -> Feistel {
+        Feistel::with_params(key, DEFAULT_ROUNDS, 128)
+    }
+
+    /// Scramble only the low `bits` bits. Panics if `bits` is not even and within
+    /// 2..=128, or if `rounds` is 0.
+    pub const fn with_params(key: u128, rounds: u32, bits: u32) -> Feistel {
+        assert!(bits >= 2 && bits <= 128 && bits & 1 == 0, "bits must be even and within 2..=128");
+        assert!(rounds > 0, "rounds must be at least 1");
+        Feistel { key, rounds, half_bits: bits / 2 }
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.half_bits * 2
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn obfuscate(&self, value: u128) -> u128 {
+        let (high, mut left, mut right) = self.split(value);
+        for round in 0..self.rounds {
+            let next = left ^ self.round_fn(round, right);
+            left = right;
+            right = next;
+        }
+        self.join(high, left, right)
+    }
+
+    pub fn deobfuscate(&self, value: u128) -> u128 {
+        let (high, mut left, mut right) = self.split(value);
+        for round in (0..self.rounds).rev() {
+            let prev = right ^ self.round_fn(round, left);
+            right = left;
+            left = prev;
+        }
+        self.join(high, left, right)
+    }
+
+    /// Obfuscate an id and turn it into its 22 char b62 representation
+    pub fn get_b62(&self, value: u128) -> [u8; 22] {
+        get_b62_from_integer(self.obfuscate(value))
+    }
+
+    /// Parse a 22 char b62 string made by `get_b62` back into the original id
+    pub fn get_integer(&self, base62: &str) -> Option<u128> {
+        Some(self.deobfuscate(get_integer(base62)?))
+    }
+
+    fn half_mask(&self) -> u64 {
+        u64::MAX >> (64 - self.half_bits)
+    }
+
+    // Returns (untouched high bits, left half, right half)
+    fn split(&self, value: u128) -> (u128, u64, u64) {
+        let bits = self.bits();
+        let high = if bits == 128 { 0 } else { value >> bits << bits };
+        let left = (value >> self.half_bits) as u64 & self.half_mask();
+        let right = value as u64 & self.half_mask();
+        (high, left, right)
+    }
+
+    fn join(&self, high: u128, left: u64, right: u64) -> u128 {
+        high | (left as u128) << self.half_bits | right as u128
+    }
+
+    fn round_fn(&self, round: u32, half: u64) -> u64 {
+        let round_key = mix64((self.key as u64) ^ mix64((self.key >> 64) as u64 ^ round as u64));
+        mix64(half ^ round_key) & self.half_mask()
+    }
+}
+
+// splitmix64 finalizer
+pub(crate) fn mix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
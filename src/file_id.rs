@@ -0,0 +1,161 @@
+//! 160 bit file ids, the SHA-1 values Spotify uses for images and audio files.
+//!
+//! ```
+//! use rb62::file_id::FileId;
+//!
+//! let file_id = FileId::from_url("https://i.scdn.co/image/ab67616d0000b273e8b066f70c206551210d902b").unwrap();
+//! assert_eq!(file_id.to_string(), "ab67616d0000b273e8b066f70c206551210d902b");
+//! assert_eq!(&file_id.to_b62(), b"osj8DrYMOzhxAuwe571gMVg8FwD");
+//! ```
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{base62_char, HEX};
+
+/// Base of the CDN urls images are served from
+pub const IMAGE_URL_BASE: &str = "https://i.scdn.co/image/";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId([u8; 20]);
+
+impl FileId {
+    pub const fn from_bytes(bytes: [u8; 20]) -> FileId {
+        FileId(bytes)
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// Parse 40 hex chars, of either case
+    pub fn from_hex(hex: &str) -> Option<FileId> {
+        let hex = hex.as_bytes();
+        if hex.len() != 40 {
+            return None;
+        }
+        let mut bytes = [0u8; 20];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let h1 = (hex[i * 2] as char).to_digit(16)? as u8;
+            let h2 = (hex[i * 2 + 1] as char).to_digit(16)? as u8;
+            *byte = h1 << 4 | h2;
+        }
+        Some(FileId(bytes))
+    }
+
+    /// The 40 char lowercase Hex String, like `get_hex` does for 128 bit ids
+    pub fn to_hex(&self) -> [u8; 40] {
+        let mut hex_val_array: [u8; 40] = [0; 40];
+        for (i, byte) in self.0.iter().enumerate() {
+            hex_val_array[i * 2] = HEX[(byte >> 4) as usize];
+            hex_val_array[i * 2 + 1] = HEX[(byte & 0x0f) as usize];
+        }
+        hex_val_array
+    }
+
+    /// Parse the 27 char b62 form made by `to_b62`
+    pub fn from_b62(base62: &str) -> Option<FileId> {
+        let base62 = base62.as_bytes();
+        if base62.len() != 27 {
+            return None;
+        }
+        // big endian u32 limbs
+        let mut limbs = [0u32; 5];
+        for c in base62 {
+            let mut carry = crate::radix::BASE62.digit_val(*c)? as u64;
+            for limb in limbs.iter_mut().rev() {
+                let current = *limb as u64 * 62 + carry;
+                *limb = current as u32;
+                carry = current >> 32;
+            }
+            if carry != 0 {
+                return None; // does not fit in 160 bits
+            }
+        }
+        let mut bytes = [0u8; 20];
+        for (chunk, limb) in bytes.chunks_mut(4).zip(limbs.iter()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        Some(FileId(bytes))
+    }
+
+    /// The 27 char b62 form, left padded with `0` like `get_b62`
+    pub fn to_b62(&self) -> [u8; 27] {
+        let mut limbs = [0u32; 5];
+        for (limb, chunk) in limbs.iter_mut().zip(self.0.chunks(4)) {
+            *limb = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        let mut b62_val_array = [b'0'; 27];
+        for c in b62_val_array.iter_mut().rev() {
+            let mut remainder = 0u64;
+            for limb in limbs.iter_mut() {
+                let current = remainder << 32 | *limb as u64;
+                *limb = (current / 62) as u32;
+                remainder = current % 62;
+            }
+            *c = base62_char(remainder as u8).unwrap(); // remainder is always below 62
+        }
+        b62_val_array
+    }
+
+    /// Find the file id in a CDN url like `https://i.scdn.co/image/<hex>`: the last path
+    /// segment, ignoring any query or fragment, has to be 40 hex chars
+    pub fn from_url(url: &str) -> Option<FileId> {
+        let url = url.split(['?', '#']).next()?;
+        let path = match url.find("://") {
+            Some(scheme_end) => &url[scheme_end + 3..],
+            None => url,
+        };
+        let (_, last_segment) = path.trim_end_matches('/').rsplit_once('/')?;
+        FileId::from_hex(last_segment)
+    }
+
+    /// Displays as `https://i.scdn.co/image/<hex>`
+    pub fn image_url(&self) -> ImageUrl {
+        ImageUrl(*self)
+    }
+}
+
+/// Returned by `FileId::image_url`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageUrl(FileId);
+
+impl fmt::Display for ImageUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", IMAGE_URL_BASE, self.0)
+    }
+}
+
+impl From<[u8; 20]> for FileId {
+    fn from(bytes: [u8; 20]) -> FileId {
+        FileId(bytes)
+    }
+}
+
+impl From<FileId> for [u8; 20] {
+    fn from(file_id: FileId) -> [u8; 20] {
+        file_id.0
+    }
+}
+
+/// Parses the 40 char hex form
+impl FromStr for FileId {
+    type Err = crate::id::ParseIdError;
+
+    fn from_str(hex: &str) -> Result<FileId, Self::Err> {
+        FileId::from_hex(hex).ok_or(crate::id::ParseIdError::new("file"))
+    }
+}
+
+/// Displays as the 40 char lowercase hex form
+impl fmt::Display for FileId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // hex is always ASCII
+        f.write_str(core::str::from_utf8(&self.to_hex()).unwrap())
+    }
+}
+
+impl fmt::Debug for FileId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FileId({})", self)
+    }
+}
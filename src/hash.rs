@@ -0,0 +1,116 @@
+//! A `BuildHasher` for maps keyed by ids, which are already uniformly random, so SipHash
+//! is pure overhead.
+//!
+//! `IdHashBuilder::new()` folds the u128 into a u64 by xoring its halves. That is only a
+//! good hash for random ids: anyone choosing the ids can make them all collide. For ids
+//! coming from untrusted input, use `IdHashBuilder::hardened` with a secret key, which
+//! mixes the key in with a folded 64 bit multiply. It resists flooding the way aHash
+//! does, it is not a cryptographic hash.
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! use rb62::hash::IdMap;
+//!
+//! let mut plays: IdMap<u32> = IdMap::default();
+//! *plays.entry(rb62::get_integer("6GGODyP2LIdbxIfYxy5UbN").unwrap()).or_default() += 1;
+//! assert_eq!(plays[&0xdbc3d5ebe344484da3e2448712a02213], 1);
+//! # }
+//! ```
+use core::hash::{BuildHasher, Hasher};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IdHashBuilder {
+    key: Option<[u64; 2]>,
+}
+
+impl IdHashBuilder {
+    /// Fast folding, for ids which are random and not chosen by an adversary
+    pub const fn new() -> IdHashBuilder {
+        IdHashBuilder { key: None }
+    }
+
+    /// Keyed mixing, for ids from untrusted input. The key has to stay secret.
+    pub const fn hardened(key: [u64; 2]) -> IdHashBuilder {
+        IdHashBuilder { key: Some(key) }
+    }
+
+    /// `hardened` with a key from the random keys std gives `HashMap`
+    #[cfg(feature = "std")]
+    pub fn random_hardened() -> IdHashBuilder {
+        let random = std::collections::hash_map::RandomState::new();
+        let key = |i| {
+            let mut hasher = random.build_hasher();
+            hasher.write_u8(i);
+            hasher.finish()
+        };
+        IdHashBuilder::hardened([key(0), key(1)])
+    }
+
+    pub fn is_hardened(&self) -> bool {
+        self.key.is_some()
+    }
+}
+
+impl BuildHasher for IdHashBuilder {
+    type Hasher = IdHasher;
+
+    fn build_hasher(&self) -> IdHasher {
+        IdHasher { key: self.key, hash: 0 }
+    }
+}
+
+/// The `Hasher` of `IdHashBuilder`. Made for one `write_u128` per key, anything else is
+/// hashed 16 bytes at a time.
+#[derive(Clone, Debug)]
+pub struct IdHasher {
+    key: Option<[u64; 2]>,
+    hash: u64,
+}
+
+impl IdHasher {
+    fn add(&mut self, value: u128) {
+        let (high, low) = ((value >> 64) as u64, value as u64);
+        self.hash = match self.key {
+            None => self.hash.rotate_left(5) ^ high ^ low,
+            Some([k0, k1]) => folded_multiply(low ^ k0, high ^ k1 ^ self.hash),
+        };
+    }
+}
+
+impl Hasher for IdHasher {
+    fn finish(&self) -> u64 {
+        match self.key {
+            None => self.hash,
+            Some([k0, k1]) => folded_multiply(self.hash ^ k1, k0 ^ 0x243f_6a88_85a3_08d3),
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(16) {
+            let mut buf = [0u8; 16];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            self.add(u128::from_le_bytes(buf));
+        }
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.add(value)
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.add(value as u128)
+    }
+}
+
+fn folded_multiply(a: u64, b: u64) -> u64 {
+    let product = a as u128 * b as u128;
+    (product >> 64) as u64 ^ product as u64
+}
+
+/// A std `HashMap` keyed by ids
+#[cfg(feature = "std")]
+pub type IdMap<V> = std::collections::HashMap<u128, V, IdHashBuilder>;
+
+/// A std `HashSet` of ids, see `rb62::id_set::IdSet` for a sorted one
+#[cfg(feature = "std")]
+pub type IdSet = std::collections::HashSet<u128, IdHashBuilder>;
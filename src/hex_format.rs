@@ -0,0 +1,137 @@
+//! Configurable hex output, for systems which want something other than the
+//! lowercase dashless 32 digits of `get_hex`.
+//!
+//! ```
+//! use rb62::hex_format::{HexFormat, MAX_HEX_LEN};
+//!
+//! let format = HexFormat::new().uppercase(true).prefix(true);
+//! let mut buf = [0u8; MAX_HEX_LEN];
+//! let hex = format.get_hex("6GGODyP2LIdbxIfYxy5UbN", &mut buf).unwrap();
+//! assert_eq!(hex, "0xDBC3D5EBE344484DA3E2448712A02213");
+//! assert_eq!(format.parse(hex), Some(0xdbc3d5ebe344484da3e2448712a02213));
+//! ```
+use crate::{get_b62_from_integer, get_integer, HEX};
+
+/// Longest possible output, `0x` followed by 32 digits and 4 dashes
+pub const MAX_HEX_LEN: usize = 38;
+
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HexFormat {
+    uppercase: bool,
+    prefix: bool,
+    dashed: bool,
+    little_endian: bool,
+}
+
+impl HexFormat {
+    /// The same format as `get_hex`: lowercase, no prefix, no dashes, big endian
+    pub const fn new() -> HexFormat {
+        HexFormat { uppercase: false, prefix: false, dashed: false, little_endian: false }
+    }
+
+    /// Use `A-F` instead of `a-f`
+    pub const fn uppercase(mut self, uppercase: bool) -> HexFormat {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Start with `0x`
+    pub const fn prefix(mut self, prefix: bool) -> HexFormat {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Group digits 8-4-4-4-12 with dashes, like UUIDs
+    pub const fn dashed(mut self, dashed: bool) -> HexFormat {
+        self.dashed = dashed;
+        self
+    }
+
+    /// Write the 16 bytes in reverse order, least significant byte first
+    pub const fn little_endian(mut self, little_endian: bool) -> HexFormat {
+        self.little_endian = little_endian;
+        self
+    }
+
+    /// Length of every hex string in this format
+    pub const fn hex_len(&self) -> usize {
+        32 + if self.prefix { 2 } else { 0 } + if self.dashed { 4 } else { 0 }
+    }
+
+    pub fn format<'a>(&self, value: u128, buf: &'a mut [u8; MAX_HEX_LEN]) -> &'a str {
+        let table = if self.uppercase { HEX_UPPER } else { *HEX };
+        let bytes = if self.little_endian { value.to_le_bytes() } else { value.to_be_bytes() };
+        let mut pos = 0;
+        if self.prefix {
+            buf[..2].copy_from_slice(b"0x");
+            pos = 2;
+        }
+        for (i, byte) in bytes.iter().enumerate() {
+            if self.dashed && is_dash_before(i) {
+                buf[pos] = b'-';
+                pos += 1;
+            }
+            buf[pos] = table[(byte >> 4) as usize];
+            buf[pos + 1] = table[(byte & 0x0f) as usize];
+            pos += 2;
+        }
+        // only ASCII was written
+        core::str::from_utf8(&buf[..pos]).unwrap()
+    }
+
+    /// Parse hex written in exactly this format, anything else (other case, missing or
+    /// extra prefix or dashes) returns None
+    pub fn parse(&self, hex: &str) -> Option<u128> {
+        let mut hex = hex.as_bytes();
+        if hex.len() != self.hex_len() {
+            return None;
+        }
+        if self.prefix {
+            hex = hex.strip_prefix(b"0x")?;
+        }
+
+        let mut bytes = [0u8; 16];
+        let mut pos = 0;
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            if self.dashed && is_dash_before(i) {
+                if hex[pos] != b'-' {
+                    return None;
+                }
+                pos += 1;
+            }
+            *byte = self.hex_val(hex[pos])? << 4 | self.hex_val(hex[pos + 1])?;
+            pos += 2;
+        }
+        if self.little_endian {
+            Some(u128::from_le_bytes(bytes))
+        } else {
+            Some(u128::from_be_bytes(bytes))
+        }
+    }
+
+    /// Turn b62 String to Hex String in this format
+    pub fn get_hex<'a>(&self, base62: &str, buf: &'a mut [u8; MAX_HEX_LEN]) -> Option<&'a str> {
+        Some(self.format(get_integer(base62)?, buf))
+    }
+
+    /// Turn Hex String in this format to b62
+    pub fn get_b62(&self, hex: &str) -> Option<[u8; 22]> {
+        Some(get_b62_from_integer(self.parse(hex)?))
+    }
+
+    fn hex_val(&self, c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' if !self.uppercase => Some(c - b'a' + 10),
+            b'A'..=b'F' if self.uppercase => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+}
+
+// Dashes go before bytes 4, 6, 8 and 10, giving 8-4-4-4-12 digits
+fn is_dash_before(byte_index: usize) -> bool {
+    byte_index == 4 || byte_index == 6 || byte_index == 8 || byte_index == 10
+}
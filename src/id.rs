@@ -0,0 +1,238 @@
+//! Typed ids, so the compiler catches a track id passed where an album id is expected.
+//!
+//! `Id<K>` is a u128 tagged with a zero sized `Kind` marker. Ids of different kinds are
+//! different types, going from one to the other takes an explicit `cast`.
+//!
+//! ```
+//! use rb62::id::{Album, Id, Track};
+//!
+//! let track: Id<Track> = "6GGODyP2LIdbxIfYxy5UbN".parse().unwrap();
+//! assert_eq!(track.to_string(), "6GGODyP2LIdbxIfYxy5UbN");
+//! assert_eq!(track.uri().to_string(), "spotify:track:6GGODyP2LIdbxIfYxy5UbN");
+//!
+//! fn album_name(album: Id<Album>) {}
+//! // album_name(track); // does not compile
+//! album_name(track.cast());
+//! ```
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use crate::{get_b62_from_integer, get_integer};
+
+/// Marker for a kind of id, implemented by zero sized types
+pub trait Kind {
+    /// Name of the kind, used in `Debug` output and errors
+    const NAME: &'static str;
+    /// Prefix of the URI form, e.g. `spotify:track:`
+    const URI_PREFIX: Option<&'static str> = None;
+}
+
+macro_rules! spotify_kinds {
+    ($($kind:ident => $name:expr),* $(,)?) => {
+        $(
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub enum $kind {}
+
+            impl Kind for $kind {
+                const NAME: &'static str = $name;
+                const URI_PREFIX: Option<&'static str> = Some(concat!("spotify:", $name, ":"));
+            }
+        )*
+    };
+}
+
+spotify_kinds! {
+    Track => "track",
+    Album => "album",
+    Artist => "artist",
+    Playlist => "playlist",
+    Show => "show",
+    Episode => "episode",
+}
+
+pub struct Id<K: Kind> {
+    value: u128,
+    kind: PhantomData<fn() -> K>,
+}
+
+impl<K: Kind> Id<K> {
+    pub const fn new(value: u128) -> Id<K> {
+        Id { value, kind: PhantomData }
+    }
+
+    /// The u128 as returned by `get_integer`
+    pub const fn value(&self) -> u128 {
+        self.value
+    }
+
+    /// Turn this id into an id of another kind with the same value
+    pub const fn cast<L: Kind>(self) -> Id<L> {
+        Id::new(self.value)
+    }
+
+    /// Parse the 22 char b62 form
+    pub fn from_b62(base62: &str) -> Option<Id<K>> {
+        Some(Id::new(get_integer(base62)?))
+    }
+
+    /// Parse the URI form, None if the kind has no URI prefix
+    pub fn from_uri(uri: &str) -> Option<Id<K>> {
+        Id::from_b62(uri.strip_prefix(K::URI_PREFIX?)?)
+    }
+
+    pub fn to_b62(&self) -> [u8; 22] {
+        get_b62_from_integer(self.value)
+    }
+
+    /// Displays as the URI form, or as plain b62 if the kind has no URI prefix
+    pub fn uri(&self) -> IdUri<K> {
+        IdUri(*self)
+    }
+}
+
+/// Returned by `Id::uri`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdUri<K: Kind>(Id<K>);
+
+impl<K: Kind> fmt::Display for IdUri<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(K::URI_PREFIX.unwrap_or(""))?;
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<K: Kind> fmt::Debug for IdUri<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Returned when parsing an `Id` fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseIdError {
+    kind: &'static str,
+}
+
+impl ParseIdError {
+    pub const fn new(kind: &'static str) -> ParseIdError {
+        ParseIdError { kind }
+    }
+
+    /// Name of the kind of id that failed to parse
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} id", self.kind)
+    }
+}
+
+/// Accepts the 22 char b62 form and the URI form
+impl<K: Kind> FromStr for Id<K> {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> Result<Id<K>, ParseIdError> {
+        Id::from_b62(s)
+            .or_else(|| Id::from_uri(s))
+            .ok_or(ParseIdError { kind: K::NAME })
+    }
+}
+
+/// Displays as the 22 char b62 form
+impl<K: Kind> fmt::Display for Id<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // b62 is always ASCII
+        f.write_str(core::str::from_utf8(&self.to_b62()).unwrap())
+    }
+}
+
+impl<K: Kind> fmt::Debug for Id<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id<{}>({})", K::NAME, self)
+    }
+}
+
+// Implemented by hand, deriving would require K to implement these as well
+
+impl<K: Kind> Clone for Id<K> {
+    fn clone(&self) -> Id<K> {
+        *self
+    }
+}
+
+impl<K: Kind> Copy for Id<K> {}
+
+impl<K: Kind> PartialEq for Id<K> {
+    fn eq(&self, other: &Id<K>) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<K: Kind> Eq for Id<K> {}
+
+impl<K: Kind> PartialOrd for Id<K> {
+    fn partial_cmp(&self, other: &Id<K>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Kind> Ord for Id<K> {
+    fn cmp(&self, other: &Id<K>) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<K: Kind> Hash for Id<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<K: Kind> From<uuid::Uuid> for Id<K> {
+    fn from(uuid: uuid::Uuid) -> Id<K> {
+        Id::new(uuid.as_u128())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<K: Kind> From<Id<K>> for uuid::Uuid {
+    fn from(id: Id<K>) -> uuid::Uuid {
+        uuid::Uuid::from_u128(id.value)
+    }
+}
+
+/// Serialized as the 22 char b62 form, deserialized from the b62 or URI form
+#[cfg(feature = "serde")]
+impl<K: Kind> serde::Serialize for Id<K> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(core::str::from_utf8(&self.to_b62()).unwrap())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K: Kind> serde::Deserialize<'de> for Id<K> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Id<K>, D::Error> {
+        struct IdVisitor<K>(PhantomData<fn() -> K>);
+
+        impl<'de, K: Kind> serde::de::Visitor<'de> for IdVisitor<K> {
+            type Value = Id<K>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a b62 {} id", K::NAME)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Id<K>, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(IdVisitor(PhantomData))
+    }
+}
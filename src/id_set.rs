@@ -0,0 +1,209 @@
+//! A set of ids kept as a sorted `Vec<u128>`, 16 bytes per id instead of a heap allocated
+//! `String` each, with lookups by b62 prefix for autocomplete.
+//!
+//! ```
+//! use rb62::id_set::IdSet;
+//!
+//! let set = IdSet::from_b62(&["6GGODyP2LIdbxIfYxy5UbN", "6GGODyP2LIdbxIfYxy5UbM", "7N42dgm5tFLK9N8MT7fHC7"]).unwrap();
+//! let matches: Vec<_> = set.b62_with_prefix("6GG").unwrap().collect();
+//! assert_eq!(matches, [*b"6GGODyP2LIdbxIfYxy5UbM", *b"6GGODyP2LIdbxIfYxy5UbN"]);
+//! ```
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::{Bound, RangeBounds};
+
+use crate::get_b62_from_integer;
+use crate::radix::BASE62;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IdSet {
+    ids: Vec<u128>,
+}
+
+impl IdSet {
+    pub fn new() -> IdSet {
+        IdSet { ids: Vec::new() }
+    }
+
+    /// Parse b62 ids, `None` if any of them is invalid
+    pub fn from_b62<I>(base62: I) -> Option<IdSet>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        base62.into_iter().map(|s| BASE62.decode(s.as_ref())).collect()
+    }
+
+    /// Parse hex ids the way `get_b62` does, `None` if any of them is invalid
+    pub fn from_hex<I>(hex: I) -> Option<IdSet>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        hex.into_iter().map(|s| u128::from_str_radix(s.as_ref(), 16).ok()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, id: u128) -> bool {
+        self.ids.binary_search(&id).is_ok()
+    }
+
+    /// Returns false if `id` was already in the set
+    pub fn insert(&mut self, id: u128) -> bool {
+        match self.ids.binary_search(&id) {
+            Ok(_) => false,
+            Err(i) => {
+                self.ids.insert(i, id);
+                true
+            }
+        }
+    }
+
+    /// Returns false if `id` was not in the set
+    pub fn remove(&mut self, id: u128) -> bool {
+        match self.ids.binary_search(&id) {
+            Ok(i) => {
+                self.ids.remove(i);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// The ids in ascending order
+    pub fn as_slice(&self) -> &[u128] {
+        &self.ids
+    }
+
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, u128>> {
+        self.ids.iter().copied()
+    }
+
+    /// The ids within `range`, in ascending order
+    pub fn range<R: RangeBounds<u128>>(&self, range: R) -> core::iter::Copied<core::slice::Iter<'_, u128>> {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.ids.partition_point(|id| id < start),
+            Bound::Excluded(start) => self.ids.partition_point(|id| id <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.ids.partition_point(|id| id <= end),
+            Bound::Excluded(end) => self.ids.partition_point(|id| id < end),
+            Bound::Unbounded => self.ids.len(),
+        };
+        self.ids[start..end.max(start)].iter().copied()
+    }
+
+    /// The ids whose 22 char b62 form starts with `prefix`, in ascending order.
+    /// `None` if `prefix` is longer than 22 chars or not b62.
+    pub fn with_prefix(&self, prefix: &str) -> Option<core::iter::Copied<core::slice::Iter<'_, u128>>> {
+        if prefix.len() > 22 {
+            return None;
+        }
+        let mut value = 0u128;
+        let mut overflow = false;
+        for c in prefix.bytes() {
+            let digit = BASE62.digit_val(c)? as u128;
+            match value.checked_mul(62).and_then(|v| v.checked_add(digit)) {
+                Some(v) => value = v,
+                None => overflow = true,
+            }
+        }
+
+        // b62 is fixed width, so a prefix covers one contiguous range of ids
+        let empty = self.ids[..0].iter().copied();
+        if overflow {
+            return Some(empty);
+        }
+        Some(match 62u128.checked_pow(22 - prefix.len() as u32) {
+            None => self.range(..),
+            Some(span) => match value.checked_mul(span) {
+                Some(low) => self.range(low..=low.saturating_add(span - 1)),
+                None => empty,
+            },
+        })
+    }
+
+    /// Like `with_prefix`, rendering each id through `get_b62_from_integer`
+    pub fn b62_with_prefix(&self, prefix: &str) -> Option<impl Iterator<Item = [u8; 22]> + '_> {
+        Some(self.with_prefix(prefix)?.map(get_b62_from_integer))
+    }
+
+    pub fn union(&self, other: &IdSet) -> IdSet {
+        let mut ids = Vec::with_capacity(self.len() + other.len());
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        loop {
+            let id = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x < y => a.next(),
+                (Some(x), Some(y)) if x > y => b.next(),
+                (Some(_), Some(_)) => {
+                    b.next();
+                    a.next()
+                }
+                (Some(_), None) => a.next(),
+                (None, Some(_)) => b.next(),
+                (None, None) => break,
+            };
+            ids.extend(id);
+        }
+        IdSet { ids }
+    }
+
+    pub fn intersection(&self, other: &IdSet) -> IdSet {
+        let (small, large) = if self.len() <= other.len() { (self, other) } else { (other, self) };
+        IdSet { ids: small.iter().filter(|id| large.contains(*id)).collect() }
+    }
+
+    /// The ids in `self` but not in `other`
+    pub fn difference(&self, other: &IdSet) -> IdSet {
+        IdSet { ids: self.iter().filter(|id| !other.contains(*id)).collect() }
+    }
+}
+
+impl FromIterator<u128> for IdSet {
+    fn from_iter<I: IntoIterator<Item = u128>>(iter: I) -> IdSet {
+        let mut ids: Vec<u128> = iter.into_iter().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        IdSet { ids }
+    }
+}
+
+impl Extend<u128> for IdSet {
+    fn extend<I: IntoIterator<Item = u128>>(&mut self, iter: I) {
+        self.ids.extend(iter);
+        self.ids.sort_unstable();
+        self.ids.dedup();
+    }
+}
+
+impl<'a> IntoIterator for &'a IdSet {
+    type Item = u128;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, u128>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for IdSet {
+    type Item = u128;
+    type IntoIter = alloc::vec::IntoIter<u128>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ids.into_iter()
+    }
+}
+
+impl From<IdSet> for Vec<u128> {
+    fn from(set: IdSet) -> Vec<u128> {
+        set.ids
+    }
+}
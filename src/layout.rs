@@ -0,0 +1,178 @@
+//! Ids made of named bit fields, like a timestamp, a shard and an entity type, which
+//! still render as 22 char b62.
+//!
+//! Fields are laid out from the most significant bit down in the order they are added,
+//! so ids sort by their first field. Bits below the last field are always zero.
+//!
+//! ```
+//! use rb62::layout::Layout;
+//!
+//! const ORDER_ID: Layout = Layout::new().field("millis", 48).field("shard", 10).field("type", 6).field("random", 64);
+//!
+//! let b62 = ORDER_ID.get_b62(&[1_600_000_000_000, 17, 3, 0x1234]).unwrap();
+//! let fields = ORDER_ID.get_fields(core::str::from_utf8(&b62).unwrap()).unwrap();
+//! assert_eq!(fields.get("shard"), Some(17));
+//! assert_eq!(fields.to_string(), "millis=1600000000000 shard=17 type=3 random=4660");
+//! ```
+use core::fmt;
+
+use crate::prefixed::str_eq;
+use crate::{get_b62_from_integer, get_integer};
+
+/// Max number of fields in a `Layout`
+pub const MAX_FIELDS: usize = 16;
+
+/// One named range of bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Field {
+    name: &'static str,
+    shift: u32,
+    width: u32,
+}
+
+impl Field {
+    const EMPTY: Field = Field { name: "", shift: 0, width: 0 };
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Number of bits
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Position of the lowest bit of the field
+    pub fn shift(&self) -> u32 {
+        self.shift
+    }
+
+    /// Largest value the field can hold
+    pub fn max(&self) -> u128 {
+        u128::MAX >> (128 - self.width)
+    }
+
+    fn get(&self, id: u128) -> u128 {
+        (id >> self.shift) & self.max()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Layout {
+    fields: [Field; MAX_FIELDS],
+    len: usize,
+    bits: u32,
+}
+
+impl Layout {
+    pub const fn new() -> Layout {
+        Layout { fields: [Field::EMPTY; MAX_FIELDS], len: 0, bits: 0 }
+    }
+
+    /// Add a field below the ones added before. Panics if the name is empty or already
+    /// used, the width is zero, the fields would need more than 128 bits, or there are
+    /// already `MAX_FIELDS` fields.
+    pub const fn field(mut self, name: &'static str, width: u32) -> Layout {
+        assert!(!name.is_empty(), "field name must not be empty");
+        assert!(width > 0, "field width must not be zero");
+        assert!(width <= 128 - self.bits, "fields must fit in 128 bits");
+        assert!(self.len < MAX_FIELDS, "too many fields");
+        let mut i = 0;
+        while i < self.len {
+            assert!(!str_eq(self.fields[i].name, name), "field name already used");
+            i += 1;
+        }
+        self.bits += width;
+        self.fields[self.len] = Field { name, shift: 128 - self.bits, width };
+        self.len += 1;
+        self
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields[..self.len]
+    }
+
+    pub fn field_by_name(&self, name: &str) -> Option<&Field> {
+        self.fields().iter().find(|field| field.name == name)
+    }
+
+    /// Number of bits used by all fields
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Pack one value per field, in the order the fields were added. `None` if the
+    /// number of values is wrong or a value does not fit its field.
+    pub fn pack(&self, values: &[u128]) -> Option<u128> {
+        if values.len() != self.len {
+            return None;
+        }
+        let mut id = 0;
+        for (field, value) in self.fields().iter().zip(values) {
+            if *value > field.max() {
+                return None;
+            }
+            id |= value << field.shift;
+        }
+        Some(id)
+    }
+
+    /// `None` if bits below the last field are set
+    pub fn unpack(&self, id: u128) -> Option<Fields<'_>> {
+        if self.bits < 128 && id & (u128::MAX >> self.bits) != 0 {
+            return None;
+        }
+        Some(Fields { layout: self, id })
+    }
+
+    /// `pack` rendered as b62
+    pub fn get_b62(&self, values: &[u128]) -> Option<[u8; 22]> {
+        Some(get_b62_from_integer(self.pack(values)?))
+    }
+
+    /// `get_integer` then `unpack`
+    pub fn get_fields(&self, base62: &str) -> Option<Fields<'_>> {
+        self.unpack(get_integer(base62)?)
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Layout {
+        Layout::new()
+    }
+}
+
+/// An id split into the fields of its layout, displays as `name=value` pairs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fields<'a> {
+    layout: &'a Layout,
+    id: u128,
+}
+
+impl<'a> Fields<'a> {
+    pub fn id(&self) -> u128 {
+        self.id
+    }
+
+    pub fn get(&self, name: &str) -> Option<u128> {
+        Some(self.layout.field_by_name(name)?.get(self.id))
+    }
+
+    /// Names and values in the order of the layout
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u128)> + 'a {
+        let id = self.id;
+        self.layout.fields().iter().map(move |field| (field.name, field.get(id)))
+    }
+}
+
+impl fmt::Display for Fields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
@@ -1,47 +1,54 @@
 #![no_std]
 use lazy_static::lazy_static;
-use specialized_div_rem::u128_div_rem_delegate; // for fast u128 dividing
 
-lazy_static! {
-    static ref MAX_VAL_ARRAY: [u8; 22] = {
-        let mut max_val_array = [0u8; 22];
-        let max_chars = "7N42dgm5tFLK9N8MT7fHC7".as_bytes(); // This will set all bits of a u128 as 1
-        for i in 0..22 {
-            max_val_array[i] = base62_val(&max_chars[i]).unwrap();
-        }
-        max_val_array
-    };
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-    static ref HEX: &'static [u8; 16] = b"0123456789abcdef";
-}
-
-pub fn get_integer(base62: &str) -> Option<u128> {
-    let mut b62_val_array: [u8; 22] = [0u8; 22];
-    let mut bi = 0u128;
+pub mod base64url;
+pub mod compact_set;
+pub mod ct;
+pub mod feistel;
+pub mod file_id;
+pub mod hash;
+pub mod hex_format;
+pub mod id;
+pub mod id_list;
+#[cfg(feature = "alloc")]
+pub mod id_set;
+pub mod layout;
+pub mod multibase;
+pub mod prefixed;
+pub mod radix;
+pub mod scan;
+#[cfg(feature = "std")]
+pub mod sequence;
+pub mod shard;
+#[cfg(feature = "std")]
+pub mod shortlink;
+pub mod snowflake;
+pub mod token;
+#[cfg(feature = "std")]
+pub mod transcode;
+#[cfg(feature = "alloc")]
+pub mod uri;
 
-    let base62 = base62.as_bytes();
-    if base62.len() != 22 {
-        return None;
-    }
+#[cfg(feature = "derive")]
+pub use rb62_derive::Base62;
 
-    for i in 0..22 {
-        b62_val_array[i] = base62_val(&base62[i])?;
-    }
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
 
-    // check input value size is no bigger than max value - "7N42dgm5tFLK9N8MT7fHC7"
-    for (val, max_val) in b62_val_array.iter().zip(MAX_VAL_ARRAY.iter()) {
-        if val > max_val {
-            return None;
-        } else if val < max_val {
-            break;
-        } // and if they are equal, continue loop to compare next val
-    }
+lazy_static! {
+    pub(crate) static ref HEX: &'static [u8; 16] = b"0123456789abcdef";
+}
 
-    for v in b62_val_array.iter() {
-        bi *= 62;
-        bi += *v as u128;
-    }
-    Some(bi)
+pub fn get_integer(base62: &str) -> Option<u128> {
+    radix::BASE62.decode(base62)
 }
 
 /// Turn b62 String to Hex String representation
@@ -50,53 +57,86 @@ pub fn get_hex(base62: &str) -> Option<[u8; 32]> {
     let hex_u128 = get_integer(base62)?;
     let hex_byte = hex_u128.to_be_bytes();
     for i in 0..16 {
-        let h1 : u8 = ((hex_byte[i]>>4) & 0x0f) as u8;
-        let h2 : u8 = (hex_byte[i] & 0x0f) as u8;
+        let h1 : u8 = (hex_byte[i]>>4) & 0x0f;
+        let h2 : u8 = hex_byte[i] & 0x0f;
         hex_val_array[i*2] = HEX[h1 as usize];
         hex_val_array[i*2+1] = HEX[h2 as usize];
     }
     Some(hex_val_array)
 }
 
-pub fn get_b62(hex: &str) -> Option<[u8; 22]> {
-    let mut b62_val_array: [u8; 22] = [0u8; 22];
+/// Turn b62 String to dashed 8-4-4-4-12 Hex String representation, the canonical UUID form
+pub fn get_uuid_hex(base62: &str) -> Option<[u8; 36]> {
+    let hex_val_array = get_hex(base62)?;
+    let mut uuid_hex_array: [u8; 36] = [b'-'; 36];
+    let mut dashes = 0;
+    for (i, h) in hex_val_array.iter().enumerate() {
+        if i == 8 || i == 12 || i == 16 || i == 20 {
+            dashes += 1;
+        }
+        uuid_hex_array[i + dashes] = *h;
+    }
+    Some(uuid_hex_array)
+}
 
-    for i in 0..22 {
-        b62_val_array[i] = 48u8;
+/// Parse a dashed 8-4-4-4-12 Hex String, hex digits can be of either case
+pub fn get_integer_from_uuid_hex(uuid_hex: &str) -> Option<u128> {
+    let uuid_hex = uuid_hex.as_bytes();
+    if uuid_hex.len() != 36 {
+        return None;
     }
 
-    let mut hex_as_u128 = u128::from_str_radix(hex, 16).ok()?;
-    let mut index = 22; // start with the last digit of 22 char b62
-    while hex_as_u128 > 0 {
-        // faster integer dividing, the code below is the same as:
-        // let result = hex_as_u128 / 62;
-        // let remainder = hex_as_u128 % 62;
-        let (result, remainder) = u128_div_rem_delegate(hex_as_u128, 62);
-        hex_as_u128 = result;
-        b62_val_array[index - 1] = base62_char(remainder as u8)?;
-        index -= 1;
+    let mut bi = 0u128;
+    for (i, c) in uuid_hex.iter().enumerate() {
+        if i == 8 || i == 13 || i == 18 || i == 23 {
+            if *c != b'-' {
+                return None;
+            }
+        } else {
+            bi = bi << 4 | (*c as char).to_digit(16)? as u128;
+        }
     }
-    Some(b62_val_array.clone())
+    Some(bi)
 }
 
-// Returns 0-61
-fn base62_val(value_char: &u8) -> Option<u8> {
-    match value_char {
-        b'0'..=b'9' => Some(*value_char as u8 - b'0'),
-        b'a'..=b'z' => Some(*value_char as u8 - b'a' + 10),
-        b'A'..=b'Z' => Some(*value_char as u8 - b'A' + 36),
-        _ => None,
-    }
+pub fn get_b62_from_uuid_hex(uuid_hex: &str) -> Option<[u8; 22]> {
+    Some(get_b62_from_integer(get_integer_from_uuid_hex(uuid_hex)?))
+}
+
+/// Turn b62 String into a `uuid::Uuid`, with the same 128 bits as `get_integer` returns
+#[cfg(feature = "uuid")]
+pub fn get_uuid(base62: &str) -> Option<uuid::Uuid> {
+    Some(uuid::Uuid::from_u128(get_integer(base62)?))
+}
+
+#[cfg(feature = "uuid")]
+pub fn get_b62_from_uuid(uuid: &uuid::Uuid) -> [u8; 22] {
+    get_b62_from_integer(uuid.as_u128())
+}
+
+pub fn get_b62(hex: &str) -> Option<[u8; 22]> {
+    let hex_as_u128 = u128::from_str_radix(hex, 16).ok()?;
+    Some(get_b62_from_integer(hex_as_u128))
+}
+
+/// Turn a u128 into its 22 char b62 representation, the reverse of `get_integer`
+pub fn get_b62_from_integer(value: u128) -> [u8; 22] {
+    radix::BASE62.encode(value)
+}
+
+/// Like `get_b62_from_integer` without the left padding, so `1` for 1 and `0` for 0
+pub fn get_b62_unpadded_from_integer(value: u128, buf: &mut [u8; 22]) -> &str {
+    radix::BASE62.encode_unpadded(value, buf)
+}
+
+/// Like `get_integer`, but also accepts b62 shorter than 22 chars
+pub fn get_integer_unpadded(base62: &str) -> Option<u128> {
+    radix::BASE62.decode_unpadded(base62)
 }
 
 // Return a char within "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
-fn base62_char(value: u8) -> Option<u8> {
-    match value {
-        0..=9 => Some(b'0' + value),
-        10..=35 => Some(b'a' + value - 10),
-        36..=61 => Some(b'A' + value - 36),
-        _ => None,
-    }
+pub(crate) fn base62_char(value: u8) -> Option<u8> {
+    radix::BASE62.digit_char(value)
 }
 
 #[cfg(test)]
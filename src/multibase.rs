@@ -0,0 +1,276 @@
+//! Self describing strings in the style of multibase, where the first char tells how
+//! the rest is encoded, e.g. `z…` for base58btc or `f…` for hex.
+//!
+//! `STANDARD` has the codes from the multibase table. b62 is not in that table, so it
+//! has to be given a code of your choosing:
+//!
+//! ```
+//! use rb62::multibase::{Encoding, MAX_ID_LEN, STANDARD};
+//!
+//! const REGISTRY: rb62::multibase::Registry = STANDARD.with(b'6', Encoding::Base62);
+//!
+//! let mut buf = [0u8; MAX_ID_LEN];
+//! let id = 0xdbc3d5ebe344484da3e2448712a02213;
+//! assert_eq!(REGISTRY.encode_id(Encoding::Base62, id, &mut buf), Some("66GGODyP2LIdbxIfYxy5UbN"));
+//! assert_eq!(REGISTRY.decode_id("zU8ycDbEa8WMR5kynpiJriE"), Some((Encoding::Base58Btc, id)));
+//! ```
+//!
+//! Like multibase, encodings work on bytes and ids are their 16 big endian bytes. The
+//! base-x style encodings (base36 and base58btc) write leading zero bytes as leading
+//! zero chars and so give ids of different lengths. `Base62` is the exception: it is
+//! the 22 char `get_b62` form and only takes exactly 16 bytes. Nothing here allocates,
+//! output goes to caller provided buffers.
+use core::convert::TryInto;
+
+use crate::base64url::BASE64URL;
+use crate::radix::{BASE36, BASE58};
+use crate::{get_b62_from_integer, get_integer, HEX};
+
+/// Longest `encode_id` output: a code char and 32 hex digits
+pub const MAX_ID_LEN: usize = 33;
+/// Max number of encodings in a `Registry`
+pub const MAX_ENCODINGS: usize = 16;
+
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+const BASE32: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE32_UPPER: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE36_UPPER: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Registry with the codes of the multibase table
+pub const STANDARD: Registry = Registry::empty()
+    .with(b'f', Encoding::Base16)
+    .with(b'F', Encoding::Base16Upper)
+    .with(b'b', Encoding::Base32)
+    .with(b'B', Encoding::Base32Upper)
+    .with(b'k', Encoding::Base36)
+    .with(b'K', Encoding::Base36Upper)
+    .with(b'z', Encoding::Base58Btc)
+    .with(b'u', Encoding::Base64Url);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Lowercase hex, decoding ignores case
+    Base16,
+    /// Uppercase hex, decoding ignores case
+    Base16Upper,
+    /// RFC 4648 base32 without padding, lowercase, decoding ignores case
+    Base32,
+    /// RFC 4648 base32 without padding, uppercase, decoding ignores case
+    Base32Upper,
+    /// Lowercase base36, decoding ignores case
+    Base36,
+    /// Uppercase base36, decoding ignores case
+    Base36Upper,
+    /// Base58 with the Bitcoin alphabet
+    Base58Btc,
+    /// RFC 4648 base64url without padding
+    Base64Url,
+    /// The 22 char `get_b62` form of 16 bytes
+    Base62,
+}
+
+/// Maps code chars to encodings, built with `Registry::empty().with(…)` or from `STANDARD`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Registry {
+    entries: [Option<(u8, Encoding)>; MAX_ENCODINGS],
+}
+
+impl Registry {
+    pub const fn empty() -> Registry {
+        Registry { entries: [None; MAX_ENCODINGS] }
+    }
+
+    /// Add an encoding under a code char. Panics if the code is not ASCII, is already
+    /// taken, or the registry is full.
+    pub const fn with(mut self, code: u8, encoding: Encoding) -> Registry {
+        assert!(code.is_ascii_graphic(), "code must be a visible ASCII char");
+        let mut i = 0;
+        while i < MAX_ENCODINGS {
+            match self.entries[i] {
+                Some((existing, _)) => assert!(existing != code, "code is already taken"),
+                None => {
+                    self.entries[i] = Some((code, encoding));
+                    return self;
+                }
+            }
+            i += 1;
+        }
+        panic!("registry is full")
+    }
+
+    /// Code char of an encoding, the first one if it is registered more than once
+    pub fn code(&self, encoding: Encoding) -> Option<u8> {
+        self.entries.iter().flatten().find(|(_, e)| *e == encoding).map(|(code, _)| *code)
+    }
+
+    pub fn encoding(&self, code: u8) -> Option<Encoding> {
+        self.entries.iter().flatten().find(|(c, _)| *c == code).map(|(_, e)| *e)
+    }
+
+    /// Encode bytes with their code char in front into `out`, returns None if the
+    /// encoding is not registered or `out` is too small
+    pub fn encode<'a>(&self, encoding: Encoding, bytes: &[u8], out: &'a mut [u8]) -> Option<&'a str> {
+        let code = self.code(encoding)?;
+        let (first, rest) = out.split_first_mut()?;
+        *first = code;
+        let len = match encoding {
+            Encoding::Base16 => encode_bits(bytes, 4, HEX.as_slice(), rest)?,
+            Encoding::Base16Upper => encode_bits(bytes, 4, HEX_UPPER, rest)?,
+            Encoding::Base32 => encode_bits(bytes, 5, BASE32, rest)?,
+            Encoding::Base32Upper => encode_bits(bytes, 5, BASE32_UPPER, rest)?,
+            Encoding::Base36 => encode_base_x(bytes, BASE36.alphabet(), rest)?,
+            Encoding::Base36Upper => encode_base_x(bytes, BASE36_UPPER, rest)?,
+            Encoding::Base58Btc => encode_base_x(bytes, BASE58.alphabet(), rest)?,
+            Encoding::Base64Url => encode_bits(bytes, 6, BASE64URL, rest)?,
+            Encoding::Base62 => {
+                let value = u128::from_be_bytes(bytes.try_into().ok()?);
+                rest.get_mut(..22)?.copy_from_slice(&get_b62_from_integer(value));
+                22
+            }
+        };
+        // only ASCII was written
+        Some(core::str::from_utf8(&out[..len + 1]).unwrap())
+    }
+
+    /// Decode a string into `out` based on its code char, returns the encoding and the
+    /// decoded bytes
+    pub fn decode<'a>(&self, encoded: &str, out: &'a mut [u8]) -> Option<(Encoding, &'a [u8])> {
+        let (code, rest) = encoded.as_bytes().split_first()?;
+        let encoding = self.encoding(*code)?;
+        let len = match encoding {
+            Encoding::Base16 | Encoding::Base16Upper => decode_bits(rest, 4, HEX_UPPER, true, out)?,
+            Encoding::Base32 | Encoding::Base32Upper => decode_bits(rest, 5, BASE32_UPPER, true, out)?,
+            Encoding::Base36 | Encoding::Base36Upper => decode_base_x(rest, BASE36_UPPER, true, out)?,
+            Encoding::Base58Btc => decode_base_x(rest, BASE58.alphabet(), false, out)?,
+            Encoding::Base64Url => decode_bits(rest, 6, BASE64URL, false, out)?,
+            Encoding::Base62 => {
+                // the code char is ASCII, so this is on a char boundary
+                let value = get_integer(&encoded[1..])?;
+                out.get_mut(..16)?.copy_from_slice(&value.to_be_bytes());
+                16
+            }
+        };
+        Some((encoding, &out[..len]))
+    }
+
+    /// Encode the 16 big endian bytes of an id
+    pub fn encode_id<'a>(&self, encoding: Encoding, id: u128, out: &'a mut [u8; MAX_ID_LEN]) -> Option<&'a str> {
+        self.encode(encoding, &id.to_be_bytes(), out)
+    }
+
+    /// Decode an id, returns None unless the string decodes to exactly 16 bytes
+    pub fn decode_id(&self, encoded: &str) -> Option<(Encoding, u128)> {
+        let mut out = [0u8; 16];
+        let (encoding, bytes) = self.decode(encoded, &mut out)?;
+        Some((encoding, u128::from_be_bytes(bytes.try_into().ok()?)))
+    }
+}
+
+// Encodings where every char holds a fixed number of bits, without padding
+fn encode_bits(bytes: &[u8], bits: u32, alphabet: &[u8], out: &mut [u8]) -> Option<usize> {
+    let mask = (1u32 << bits) - 1;
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    let mut len = 0;
+    for byte in bytes {
+        buffer = buffer << 8 | *byte as u32;
+        buffered += 8;
+        while buffered >= bits {
+            buffered -= bits;
+            *out.get_mut(len)? = alphabet[(buffer >> buffered & mask) as usize];
+            len += 1;
+        }
+    }
+    if buffered > 0 {
+        *out.get_mut(len)? = alphabet[(buffer << (bits - buffered) & mask) as usize];
+        len += 1;
+    }
+    Some(len)
+}
+
+fn decode_bits(encoded: &[u8], bits: u32, alphabet: &[u8], ignore_case: bool, out: &mut [u8]) -> Option<usize> {
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    let mut len = 0;
+    for c in encoded {
+        buffer = buffer << bits | digit_val(alphabet, *c, ignore_case)? as u32;
+        buffered += bits;
+        if buffered >= 8 {
+            buffered -= 8;
+            *out.get_mut(len)? = (buffer >> buffered) as u8;
+            len += 1;
+        }
+        buffer &= (1 << buffered) - 1;
+    }
+    // left over bits must be padding, which is less than a char and all 0
+    if buffered >= bits || buffer != 0 {
+        return None;
+    }
+    Some(len)
+}
+
+// Encodings which treat the bytes as one big number, like base58btc, with every
+// leading zero byte written as a leading zero char
+fn encode_base_x(bytes: &[u8], alphabet: &[u8], out: &mut [u8]) -> Option<usize> {
+    let radix = alphabet.len() as u32;
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+    if zeros > out.len() {
+        return None;
+    }
+    let (zero_chars, digits) = out.split_at_mut(zeros);
+    zero_chars.fill(alphabet[0]);
+
+    // digit values, least significant first
+    let mut len = 0;
+    for byte in &bytes[zeros..] {
+        let mut carry = *byte as u32;
+        for digit in digits[..len].iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % radix) as u8;
+            carry /= radix;
+        }
+        while carry > 0 {
+            *digits.get_mut(len)? = (carry % radix) as u8;
+            len += 1;
+            carry /= radix;
+        }
+    }
+    digits[..len].reverse();
+    for digit in digits[..len].iter_mut() {
+        *digit = alphabet[*digit as usize];
+    }
+    Some(zeros + len)
+}
+
+fn decode_base_x(encoded: &[u8], alphabet: &[u8], ignore_case: bool, out: &mut [u8]) -> Option<usize> {
+    let radix = alphabet.len() as u32;
+    let zeros = encoded.iter().take_while(|c| **c == alphabet[0]).count();
+    if zeros > out.len() {
+        return None;
+    }
+    let (zero_bytes, bytes) = out.split_at_mut(zeros);
+    zero_bytes.fill(0);
+
+    // bytes, least significant first
+    let mut len = 0;
+    for c in &encoded[zeros..] {
+        let mut carry = digit_val(alphabet, *c, ignore_case)? as u32;
+        for byte in bytes[..len].iter_mut() {
+            carry += *byte as u32 * radix;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            *bytes.get_mut(len)? = carry as u8;
+            len += 1;
+            carry >>= 8;
+        }
+    }
+    bytes[..len].reverse();
+    Some(zeros + len)
+}
+
+fn digit_val(alphabet: &[u8], c: u8, ignore_case: bool) -> Option<u8> {
+    let c = if ignore_case { c.to_ascii_uppercase() } else { c };
+    alphabet.iter().position(|a| *a == c).map(|val| val as u8)
+}
@@ -0,0 +1,195 @@
+//! Stripe style ids with a short type prefix, like `trk_6GGODyP2LIdbxIfYxy5UbN`.
+//!
+//! Only prefixes added to a `PrefixRegistry` are accepted, so a typo in the prefix or
+//! an id of the wrong type is caught while parsing:
+//!
+//! ```
+//! use rb62::prefixed::{PrefixRegistry, PrefixedIdError, MAX_PREFIXED_ID_LEN};
+//!
+//! const IDS: PrefixRegistry = PrefixRegistry::new(b'_').with("trk").with("alb");
+//!
+//! let id = IDS.parse("trk_6GGODyP2LIdbxIfYxy5UbN").unwrap();
+//! assert_eq!((id.prefix(), id.value()), ("trk", 0xdbc3d5ebe344484da3e2448712a02213));
+//! assert_eq!(
+//!     IDS.parse_expecting("alb", "trk_6GGODyP2LIdbxIfYxy5UbN"),
+//!     Err(PrefixedIdError::PrefixMismatch { expected: "alb", found: "trk" })
+//! );
+//!
+//! let mut buf = [0u8; MAX_PREFIXED_ID_LEN];
+//! assert_eq!(IDS.format("alb", 1, &mut buf), Ok("alb_0000000000000000000001"));
+//! ```
+use core::fmt;
+
+use crate::{get_b62_from_integer, get_integer};
+
+/// Max length of a prefix
+pub const MAX_PREFIX_LEN: usize = 16;
+/// Max number of prefixes in a `PrefixRegistry`
+pub const MAX_PREFIXES: usize = 32;
+/// Max length of a whole prefixed id, used to size stack buffers
+pub const MAX_PREFIXED_ID_LEN: usize = MAX_PREFIX_LEN + 1 + 22;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrefixedIdError {
+    /// There is no separator between prefix and body
+    MissingSeparator,
+    /// The prefix is not in the registry
+    UnknownPrefix,
+    /// The prefix is in the registry, but another one was expected
+    PrefixMismatch { expected: &'static str, found: &'static str },
+    /// The body is not a valid 22 char b62
+    InvalidBody,
+}
+
+impl fmt::Display for PrefixedIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefixedIdError::MissingSeparator => write!(f, "missing separator between prefix and id"),
+            PrefixedIdError::UnknownPrefix => write!(f, "unknown id prefix"),
+            PrefixedIdError::PrefixMismatch { expected, found } => {
+                write!(f, "expected an id with prefix {}, found prefix {}", expected, found)
+            }
+            PrefixedIdError::InvalidBody => write!(f, "invalid b62 id after the prefix"),
+        }
+    }
+}
+
+/// A parsed prefixed id, the prefix is always one from the registry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PrefixedId {
+    prefix: &'static str,
+    separator: u8,
+    value: u128,
+}
+
+impl PrefixedId {
+    pub fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /// The u128 as returned by `get_integer`
+    pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    pub fn encode<'a>(&self, buf: &'a mut [u8; MAX_PREFIXED_ID_LEN]) -> &'a str {
+        let prefix = self.prefix.as_bytes();
+        let body_start = prefix.len() + 1;
+        let end = body_start + 22;
+        buf[..prefix.len()].copy_from_slice(prefix);
+        buf[prefix.len()] = self.separator;
+        buf[body_start..end].copy_from_slice(&get_b62_from_integer(self.value));
+        // only ASCII was written
+        core::str::from_utf8(&buf[..end]).unwrap()
+    }
+}
+
+impl fmt::Display for PrefixedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; MAX_PREFIXED_ID_LEN];
+        f.write_str(self.encode(&mut buf))
+    }
+}
+
+/// The set of allowed prefixes and the separator between prefix and body
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrefixRegistry {
+    separator: u8,
+    prefixes: [Option<&'static str>; MAX_PREFIXES],
+}
+
+impl PrefixRegistry {
+    /// Panics if the separator is a b62 char or not visible ASCII
+    pub const fn new(separator: u8) -> PrefixRegistry {
+        assert!(
+            separator.is_ascii_graphic() && !separator.is_ascii_alphanumeric(),
+            "separator must be visible ASCII and not a b62 char"
+        );
+        PrefixRegistry { separator, prefixes: [None; MAX_PREFIXES] }
+    }
+
+    /// Allow a prefix. Panics if the prefix is empty, longer than `MAX_PREFIX_LEN`,
+    /// not made of ASCII letters and digits, already added, or the registry is full.
+    pub const fn with(mut self, prefix: &'static str) -> PrefixRegistry {
+        let bytes = prefix.as_bytes();
+        assert!(!bytes.is_empty() && bytes.len() <= MAX_PREFIX_LEN, "invalid prefix length");
+        let mut i = 0;
+        while i < bytes.len() {
+            assert!(bytes[i].is_ascii_alphanumeric(), "prefix must be ASCII letters and digits");
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < MAX_PREFIXES {
+            match self.prefixes[i] {
+                Some(existing) => assert!(!str_eq(existing, prefix), "prefix is already added"),
+                None => {
+                    self.prefixes[i] = Some(prefix);
+                    return self;
+                }
+            }
+            i += 1;
+        }
+        panic!("registry is full")
+    }
+
+    pub fn separator(&self) -> u8 {
+        self.separator
+    }
+
+    /// Look up a registered prefix
+    pub fn prefix(&self, prefix: &str) -> Option<&'static str> {
+        self.prefixes.iter().flatten().find(|p| **p == prefix).copied()
+    }
+
+    /// Parse an id with any registered prefix
+    pub fn parse(&self, prefixed_id: &str) -> Result<PrefixedId, PrefixedIdError> {
+        let (prefix, body) = prefixed_id
+            .split_once(self.separator as char)
+            .ok_or(PrefixedIdError::MissingSeparator)?;
+        let prefix = self.prefix(prefix).ok_or(PrefixedIdError::UnknownPrefix)?;
+        let value = get_integer(body).ok_or(PrefixedIdError::InvalidBody)?;
+        Ok(PrefixedId { prefix, separator: self.separator, value })
+    }
+
+    /// Parse an id which must have the given prefix
+    pub fn parse_expecting(&self, expected: &str, prefixed_id: &str) -> Result<u128, PrefixedIdError> {
+        let expected = self.prefix(expected).ok_or(PrefixedIdError::UnknownPrefix)?;
+        let id = self.parse(prefixed_id)?;
+        if id.prefix != expected {
+            return Err(PrefixedIdError::PrefixMismatch { expected, found: id.prefix });
+        }
+        Ok(id.value)
+    }
+
+    /// Create an id with a registered prefix
+    pub fn id(&self, prefix: &str, value: u128) -> Result<PrefixedId, PrefixedIdError> {
+        let prefix = self.prefix(prefix).ok_or(PrefixedIdError::UnknownPrefix)?;
+        Ok(PrefixedId { prefix, separator: self.separator, value })
+    }
+
+    /// Write an id with a registered prefix into `buf`
+    pub fn format<'a>(
+        &self,
+        prefix: &str,
+        value: u128,
+        buf: &'a mut [u8; MAX_PREFIXED_ID_LEN],
+    ) -> Result<&'a str, PrefixedIdError> {
+        Ok(self.id(prefix, value)?.encode(buf))
+    }
+}
+
+pub(crate) const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,210 @@
+//! Fixed width encoding of u128 in any radix, with ready made instances for the
+//! alphabets people use for 128 bit ids.
+//!
+//! A `Radix<W>` always writes `W` chars, left padded with the zero char of its
+//! alphabet, where `W` is the number of digits u128::MAX needs. The width is checked
+//! when the instance is built, so a mismatching `W` fails to compile for consts:
+//!
+//! ```
+//! use rb62::radix::{Radix, BASE58};
+//!
+//! const HEX: Radix<32> = Radix::new(b"0123456789abcdef").case_insensitive();
+//! assert_eq!(HEX.decode("DBC3D5EBE344484DA3E2448712A02213"), Some(0xdbc3d5ebe344484da3e2448712a02213));
+//! assert_eq!(&BASE58.encode(0xdbc3d5ebe344484da3e2448712a02213), b"U8ycDbEa8WMR5kynpiJriE");
+//! ```
+use specialized_div_rem::u128_div_rem_delegate; // for fast u128 dividing
+
+/// The alphabet of `get_b62`: digits, then lowercase, then uppercase
+pub const BASE62: Radix<22> =
+    Radix::new(b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
+
+/// Digits then lowercase letters, decoding ignores case
+pub const BASE36: Radix<25> = Radix::new(b"0123456789abcdefghijklmnopqrstuvwxyz").case_insensitive();
+
+/// The Bitcoin alphabet, without `0`, `O`, `I` and `l`
+pub const BASE58: Radix<22> =
+    Radix::new(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+/// Douglas Crockford's base32. Decoding ignores case and reads `I` and `L` as `1` and
+/// `O` as `0`. Hyphens and the check symbol are not supported, as ids are fixed width.
+pub const CROCKFORD32: Radix<26> = Radix::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ")
+    .case_insensitive()
+    .alias(b'I', b'1')
+    .alias(b'i', b'1')
+    .alias(b'L', b'1')
+    .alias(b'l', b'1')
+    .alias(b'O', b'0')
+    .alias(b'o', b'0');
+
+const INVALID: u8 = 0xff;
+
+/// Number of digits in the given radix needed to write every u128
+pub const fn width(radix: usize) -> usize {
+    assert!(radix >= 2, "radix must be at least 2");
+    let mut value = u128::MAX;
+    let mut digits = 0;
+    while value > 0 {
+        value /= radix as u128;
+        digits += 1;
+    }
+    digits
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Radix<const W: usize> {
+    alphabet: &'static [u8],
+    values: [u8; 256],
+    max_val_array: [u8; W],
+}
+
+impl<const W: usize> Radix<W> {
+    /// Panics if the alphabet is shorter than 2 or longer than 255 chars, has a char
+    /// twice or is not ASCII, or if `W` is not `width(alphabet.len())`.
+    pub const fn new(alphabet: &'static [u8]) -> Radix<W> {
+        assert!(alphabet.len() >= 2 && alphabet.len() < 256, "invalid alphabet length");
+        assert!(W == width(alphabet.len()), "width does not match the alphabet");
+
+        let mut values = [INVALID; 256];
+        let mut i = 0;
+        while i < alphabet.len() {
+            let c = alphabet[i] as usize;
+            assert!(c < 128, "alphabet must be ASCII");
+            assert!(values[c] == INVALID, "alphabet has a char twice");
+            values[c] = i as u8;
+            i += 1;
+        }
+
+        // The digits of u128::MAX, which sets all bits of a u128 as 1
+        let mut max_val_array = [0u8; W];
+        let mut value = u128::MAX;
+        let mut index = W;
+        while index > 0 {
+            max_val_array[index - 1] = (value % alphabet.len() as u128) as u8;
+            value /= alphabet.len() as u128;
+            index -= 1;
+        }
+
+        Radix { alphabet, values, max_val_array }
+    }
+
+    /// Also decode `from` as the digit `to`. Panics if `to` is not in the alphabet or
+    /// `from` already means something else.
+    pub const fn alias(mut self, from: u8, to: u8) -> Radix<W> {
+        let val = self.values[to as usize];
+        assert!(val != INVALID, "alias target is not in the alphabet");
+        assert!(
+            self.values[from as usize] == INVALID || self.values[from as usize] == val,
+            "alias source is already in use"
+        );
+        self.values[from as usize] = val;
+        self
+    }
+
+    /// Also decode letters of the alphabet in the other case. Panics if that would
+    /// make a char ambiguous, like for base62.
+    pub const fn case_insensitive(mut self) -> Radix<W> {
+        let mut i = 0;
+        while i < self.alphabet.len() {
+            let c = self.alphabet[i];
+            let other = if c.is_ascii_lowercase() {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            };
+            if other != c {
+                self = self.alias(other, c);
+            }
+            i += 1;
+        }
+        self
+    }
+
+    pub const fn radix(&self) -> usize {
+        self.alphabet.len()
+    }
+
+    pub const fn alphabet(&self) -> &'static [u8] {
+        self.alphabet
+    }
+
+    /// Value of a single char, aliases included
+    pub fn digit_val(&self, c: u8) -> Option<u8> {
+        match self.values[c as usize] {
+            INVALID => None,
+            val => Some(val),
+        }
+    }
+
+    /// Char of a single digit value
+    pub fn digit_char(&self, val: u8) -> Option<u8> {
+        self.alphabet.get(val as usize).copied()
+    }
+
+    pub fn decode(&self, encoded: &str) -> Option<u128> {
+        let mut val_array = [0u8; W];
+        let mut bi = 0u128;
+
+        let encoded = encoded.as_bytes();
+        if encoded.len() != W {
+            return None;
+        }
+
+        for (val, c) in val_array.iter_mut().zip(encoded.iter()) {
+            *val = self.digit_val(*c)?;
+        }
+
+        // check input value size is no bigger than max value
+        for (val, max_val) in val_array.iter().zip(self.max_val_array.iter()) {
+            if val > max_val {
+                return None;
+            } else if val < max_val {
+                break;
+            } // and if they are equal, continue loop to compare next val
+        }
+
+        for v in val_array.iter() {
+            bi *= self.radix() as u128;
+            bi += *v as u128;
+        }
+        Some(bi)
+    }
+
+    /// Decode 1 to `W` chars, shorter input reads as if left padded with the zero char
+    pub fn decode_unpadded(&self, encoded: &str) -> Option<u128> {
+        let len = encoded.len();
+        if len == W {
+            return self.decode(encoded);
+        }
+        if len == 0 || len > W {
+            return None;
+        }
+        // fewer than W digits always fit in a u128, no need to check against the max
+        let radix = self.radix() as u128;
+        encoded.bytes().try_fold(0u128, |bi, c| Some(bi * radix + self.digit_val(c)? as u128))
+    }
+
+    pub fn encode(&self, mut value: u128) -> [u8; W] {
+        let mut char_array = [self.alphabet[0]; W];
+        let radix = self.radix() as u128;
+
+        let mut index = W; // start with the last digit
+        while value > 0 {
+            // faster integer dividing, the code below is the same as:
+            // let result = value / radix;
+            // let remainder = value % radix;
+            let (result, remainder) = u128_div_rem_delegate(value, radix);
+            value = result;
+            char_array[index - 1] = self.alphabet[remainder as usize];
+            index -= 1;
+        }
+        char_array
+    }
+
+    /// `encode` without the left padding, zero is a single zero char
+    pub fn encode_unpadded<'a>(&self, value: u128, buf: &'a mut [u8; W]) -> &'a str {
+        *buf = self.encode(value);
+        let start = buf.iter().position(|c| *c != self.alphabet[0]).unwrap_or(W - 1);
+        // only ASCII was written
+        core::str::from_utf8(&buf[start..]).unwrap()
+    }
+}
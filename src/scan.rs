@@ -0,0 +1,238 @@
+//! Find b62 ids, hex gids and Spotify URIs inside arbitrary text, like logs and support
+//! tickets, and optionally rewrite them into another representation.
+//!
+//! A naive regex for 22 alphanumeric chars matches plenty of ordinary words. Here a hit
+//! has to be a whole word (no ASCII letter or digit right before or after it), and 22
+//! char candidates have to pass `get_integer`, which rejects most words as too large.
+//!
+//! ```
+//! use rb62::scan::{scan, HitKind};
+//!
+//! let text = b"play spotify:track:6GGODyP2LIdbxIfYxy5UbN (gid dbc3d5ebe344484da3e2448712a02213)";
+//! let hits: Vec<_> = scan(text).map(|hit| (hit.kind, hit.id)).collect();
+//! assert_eq!(hits, [
+//!     (HitKind::Uri, Some(0xdbc3d5ebe344484da3e2448712a02213)),
+//!     (HitKind::Hex, Some(0xdbc3d5ebe344484da3e2448712a02213)),
+//! ]);
+//! ```
+use core::ops::Range;
+
+use crate::hex_format::{HexFormat, MAX_HEX_LEN};
+use crate::{get_b62_from_integer, get_integer};
+
+const URI_KINDS: [&[u8]; 6] = [b"track", b"album", b"artist", b"playlist", b"show", b"episode"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HitKind {
+    /// A 22 char b62 id
+    Base62,
+    /// A 32 char hex gid, of either case
+    Hex,
+    /// A Spotify URI, see `rb62::uri` for the forms
+    Uri,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hit {
+    pub kind: HitKind,
+    /// Where the whole hit is in the text
+    pub span: Range<usize>,
+    /// Where the id is in the text, the same as `span` except for URIs. None for URIs
+    /// without an id, like user collections and local files.
+    pub id_span: Option<Range<usize>>,
+    pub id: Option<u128>,
+}
+
+/// Representations `rewrite` can turn ids into
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// 22 char b62
+    Base62,
+    /// 32 char lowercase hex
+    Hex,
+    /// Dashed 8-4-4-4-12 hex
+    UuidHex,
+}
+
+impl Format {
+    /// Write the id in this format into `buf`
+    pub fn write<'a>(&self, id: u128, buf: &'a mut [u8; MAX_HEX_LEN]) -> &'a [u8] {
+        match self {
+            Format::Base62 => {
+                buf[..22].copy_from_slice(&get_b62_from_integer(id));
+                &buf[..22]
+            }
+            Format::Hex => HexFormat::new().format(id, buf).as_bytes(),
+            Format::UuidHex => HexFormat::new().dashed(true).format(id, buf).as_bytes(),
+        }
+    }
+}
+
+/// Iterate over the hits in a text, in order and without overlaps
+pub fn scan(text: &[u8]) -> Scanner<'_> {
+    Scanner { text, pos: 0 }
+}
+
+/// Returned by `scan`
+#[derive(Clone, Debug)]
+pub struct Scanner<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Hit;
+
+    fn next(&mut self) -> Option<Hit> {
+        let text = self.text;
+        while self.pos < text.len() {
+            let start = self.pos;
+            if (start > 0 && is_word_char(text[start - 1])) || !is_word_char(text[start]) {
+                self.pos += 1;
+                continue;
+            }
+
+            if let Some(hit) = match_uri(text, start) {
+                self.pos = hit.span.end;
+                return Some(hit);
+            }
+
+            let end = start + text[start..].iter().take_while(|c| is_word_char(**c)).count();
+            self.pos = end;
+            let word = &text[start..end];
+            let id = match word.len() {
+                // b62 chars are ASCII, so this is valid UTF-8
+                22 => get_integer(core::str::from_utf8(word).unwrap()).map(|id| (HitKind::Base62, id)),
+                32 => parse_hex(word).map(|id| (HitKind::Hex, id)),
+                _ => None,
+            };
+            if let Some((kind, id)) = id {
+                return Some(Hit { kind, span: start..end, id_span: Some(start..end), id: Some(id) });
+            }
+        }
+        None
+    }
+}
+
+/// Copy `text` to `write`, replacing the id of every hit for which `choose` returns a
+/// format. Everything else, including the rest of a URI, is copied as it is.
+pub fn rewrite<C, W>(text: &[u8], mut choose: C, mut write: W)
+where
+    C: FnMut(&Hit) -> Option<Format>,
+    W: FnMut(&[u8]),
+{
+    let mut copied = 0;
+    for hit in scan(text) {
+        let (id_span, id) = match (&hit.id_span, hit.id) {
+            (Some(id_span), Some(id)) => (id_span.clone(), id),
+            _ => continue,
+        };
+        if let Some(format) = choose(&hit) {
+            let mut buf = [0u8; MAX_HEX_LEN];
+            write(&text[copied..id_span.start]);
+            write(format.write(id, &mut buf));
+            copied = id_span.end;
+        }
+    }
+    write(&text[copied..]);
+}
+
+/// `rewrite` into a new Vec
+#[cfg(feature = "alloc")]
+pub fn rewrite_to_vec<C: FnMut(&Hit) -> Option<Format>>(text: &[u8], choose: C) -> alloc::vec::Vec<u8> {
+    let mut out = alloc::vec::Vec::with_capacity(text.len());
+    rewrite(text, choose, |bytes| out.extend_from_slice(bytes));
+    out
+}
+
+fn is_word_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+// Chars of a percent encoded user name or local file field
+fn is_segment_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'%' | b'+' | b'-' | b'.' | b'_' | b'~' | b'*')
+}
+
+fn parse_hex(word: &[u8]) -> Option<u128> {
+    let mut value = 0u128;
+    for c in word {
+        value = value << 4 | (*c as char).to_digit(16)? as u128;
+    }
+    Some(value)
+}
+
+// Small cursor over the text for matching URIs
+struct Cursor<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn literal(&mut self, literal: &[u8]) -> Option<()> {
+        if self.text[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> Range<usize> {
+        let start = self.pos;
+        self.pos += self.text[start..].iter().take_while(|c| f(**c)).count();
+        start..self.pos
+    }
+
+    // a b62 id that is a whole word
+    fn id(&mut self) -> Option<(Range<usize>, u128)> {
+        let span = self.take_while(is_word_char);
+        let id = get_integer(core::str::from_utf8(&self.text[span.clone()]).ok()?)?;
+        Some((span, id))
+    }
+
+    // true if the URI ends here, i.e. no letter, digit or `:` follows
+    fn at_end(&self) -> bool {
+        match self.text.get(self.pos) {
+            Some(c) => !is_word_char(*c) && *c != b':',
+            None => true,
+        }
+    }
+}
+
+fn match_uri(text: &[u8], start: usize) -> Option<Hit> {
+    let mut cursor = Cursor { text, pos: start };
+    cursor.literal(b"spotify:")?;
+
+    let (id_span, id) = if cursor.literal(b"user:").is_some() {
+        cursor.take_while(is_segment_char);
+        cursor.literal(b":")?;
+        if cursor.literal(b"collection").is_some() {
+            (None, None)
+        } else {
+            cursor.literal(b"playlist:")?;
+            let (span, id) = cursor.id()?;
+            (Some(span), Some(id))
+        }
+    } else if cursor.literal(b"local:").is_some() {
+        for _ in 0..3 {
+            cursor.take_while(is_segment_char);
+            cursor.literal(b":")?;
+        }
+        if cursor.take_while(|c| c.is_ascii_digit()).is_empty() {
+            return None;
+        }
+        (None, None)
+    } else {
+        let kind = URI_KINDS.iter().find(|kind| text[cursor.pos..].starts_with(kind))?;
+        cursor.literal(kind)?;
+        cursor.literal(b":")?;
+        let (span, id) = cursor.id()?;
+        (Some(span), Some(id))
+    };
+
+    if !cursor.at_end() {
+        return None;
+    }
+    Some(Hit { kind: HitKind::Uri, span: start..cursor.pos, id_span, id })
+}
@@ -0,0 +1,195 @@
+//! Sequential ids for short links and ticket numbers, handed out from blocks reserved in
+//! a `Storage` so the storage is only written once per block.
+//!
+//! The storage keeps a high water mark, the first value not reserved yet. After a restart
+//! allocation continues from there, so the unused rest of a block is skipped but no value
+//! is ever handed out twice.
+//!
+//! ```
+//! use rb62::sequence::{MemoryStorage, SequenceAllocator};
+//!
+//! let allocator = SequenceAllocator::new(MemoryStorage::new(), 100).unwrap();
+//! assert_eq!(allocator.next().unwrap(), 0);
+//! assert_eq!(allocator.next_code().unwrap(), "1");
+//! assert_eq!(allocator.high_water_mark(), 100);
+//! ```
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::sync::Mutex;
+
+use crate::get_b62_unpadded_from_integer;
+
+/// Where the high water mark of a `SequenceAllocator` is kept
+pub trait Storage {
+    type Error;
+
+    /// The stored high water mark, `None` if nothing was stored yet
+    fn load(&mut self) -> Result<Option<u128>, Self::Error>;
+
+    /// Store the high water mark, it has to be durable when this returns
+    fn store(&mut self, high_water_mark: u128) -> Result<(), Self::Error>;
+}
+
+/// Keeps the high water mark in memory only, for tests and single process use
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStorage {
+    value: Option<u128>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> MemoryStorage {
+        MemoryStorage { value: None }
+    }
+
+    /// Start from a high water mark
+    pub fn with_value(value: u128) -> MemoryStorage {
+        MemoryStorage { value: Some(value) }
+    }
+}
+
+impl Storage for MemoryStorage {
+    type Error = core::convert::Infallible;
+
+    fn load(&mut self) -> Result<Option<u128>, Self::Error> {
+        Ok(self.value)
+    }
+
+    fn store(&mut self, high_water_mark: u128) -> Result<(), Self::Error> {
+        self.value = Some(high_water_mark);
+        Ok(())
+    }
+}
+
+/// Keeps the high water mark as decimal text in a file. Stores write a temporary file
+/// next to it, sync it, rename it over the file and on unix sync the directory, so a
+/// crash leaves either the old or the new value.
+#[derive(Clone, Debug)]
+pub struct FileStorage {
+    path: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new<P: AsRef<Path>>(path: P) -> FileStorage {
+        FileStorage { path: path.as_ref().to_path_buf() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Storage for FileStorage {
+    type Error = io::Error;
+
+    fn load(&mut self) -> io::Result<Option<u128>> {
+        match fs::read_to_string(&self.path) {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid high water mark")),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn store(&mut self, high_water_mark: u128) -> io::Result<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let mut file = File::create(&tmp)?;
+        writeln!(file, "{}", high_water_mark)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        // the rename is only durable once the directory is synced
+        #[cfg(unix)]
+        {
+            let dir = match self.path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SequenceError<E> {
+    Storage(E),
+    /// No values are left below u128::MAX
+    Exhausted,
+}
+
+impl<E: fmt::Display> fmt::Display for SequenceError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceError::Storage(err) => write!(f, "sequence storage error: {}", err),
+            SequenceError::Exhausted => write!(f, "sequence exhausted"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for SequenceError<E> {}
+
+struct State<S> {
+    storage: S,
+    next: u128,
+    limit: u128,
+}
+
+/// Hands out increasing values, safe to share between threads
+pub struct SequenceAllocator<S> {
+    state: Mutex<State<S>>,
+    block_size: u128,
+}
+
+impl<S: Storage> SequenceAllocator<S> {
+    /// Continue from the high water mark in `storage`, or from 0. Panics if `block_size`
+    /// is 0.
+    pub fn new(mut storage: S, block_size: u128) -> Result<SequenceAllocator<S>, S::Error> {
+        assert!(block_size > 0, "block size must not be zero");
+        let start = storage.load()?.unwrap_or(0);
+        Ok(SequenceAllocator { state: Mutex::new(State { storage, next: start, limit: start }), block_size })
+    }
+
+    pub fn next(&self) -> Result<u128, SequenceError<S::Error>> {
+        // the state is only changed after the storage succeeded, so it is fine to go on
+        // after a panic in another thread
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        if state.next == state.limit {
+            let limit = state.limit.saturating_add(self.block_size);
+            if limit == state.limit {
+                return Err(SequenceError::Exhausted);
+            }
+            state.storage.store(limit).map_err(SequenceError::Storage)?;
+            state.limit = limit;
+        }
+        let value = state.next;
+        state.next += 1;
+        Ok(value)
+    }
+
+    /// `next` as variable width b62
+    pub fn next_code(&self) -> Result<String, SequenceError<S::Error>> {
+        let mut buf = [0u8; 22];
+        Ok(get_b62_unpadded_from_integer(self.next()?, &mut buf).to_string())
+    }
+
+    /// The first value not reserved yet, as last stored
+    pub fn high_water_mark(&self) -> u128 {
+        self.state.lock().unwrap_or_else(|err| err.into_inner()).limit
+    }
+
+    pub fn into_storage(self) -> S {
+        self.state.into_inner().unwrap_or_else(|err| err.into_inner()).storage
+    }
+}
+
+/// Write `value` in b62 without leading zeros, `0` for zero. The same as
+/// `get_b62_unpadded_from_integer`.
+pub fn get_code(value: u128, buf: &mut [u8; 22]) -> &str {
+    get_b62_unpadded_from_integer(value, buf)
+}
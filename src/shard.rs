@@ -0,0 +1,95 @@
+//! Assigning ids to shards the same way in every language.
+//!
+//! Everything here is defined on unsigned 64 bit integers with wrapping arithmetic, so
+//! it ports directly to any language with such integers (or `& 0xffffffffffffffff`
+//! after each step, as in Python). `tests/data/shard_vectors.csv` has test vectors to
+//! check other implementations against.
+//!
+//! **fold(id)**: the 128 bit id as `get_integer` returns it, with its high 64 bits xored
+//! into its low 64 bits.
+//!
+//! **mix(z)**: the splitmix64 step.
+//! ```text
+//! z = z + 0x9e3779b97f4a7c15
+//! z = (z ^ (z >> 30)) * 0xbf58476d1ce4e5b9
+//! z = (z ^ (z >> 27)) * 0x94d049bb133111eb
+//! return z ^ (z >> 31)
+//! ```
+//!
+//! **jump_hash(id, buckets)**: Lamping and Veach's jump consistent hash of `fold(id)`,
+//! with IEEE 754 doubles for the division as in their paper. Going from `n` to `n + 1`
+//! buckets only moves ids into the new bucket.
+//! ```text
+//! key = fold(id), b = -1, j = 0
+//! while j < buckets:
+//!     b = j
+//!     key = key * 2862933555777941757 + 1
+//!     j = floor(double(b + 1) * (double(1 << 31) / double((key >> 33) + 1)))
+//! return b
+//! ```
+//!
+//! **node_hash(name)**: 64 bit FNV-1a of the UTF-8 bytes of a node name.
+//! ```text
+//! h = 0xcbf29ce484222325
+//! for byte in name: h = (h ^ byte) * 0x100000001b3
+//! ```
+//!
+//! **rendezvous(id, nodes)**: the index of the node with the highest
+//! `mix((id >> 64) ^ mix((id & 0xffffffffffffffff) ^ node_hash(name)))`, the first one on
+//! a tie. Removing a node only moves the ids which were on it.
+//!
+//! ```
+//! use rb62::shard::{jump_hash, rendezvous};
+//!
+//! let id = rb62::get_integer("6GGODyP2LIdbxIfYxy5UbN").unwrap();
+//! assert_eq!(jump_hash(id, 10), Some(4));
+//! assert_eq!(rendezvous(id, &["db-1", "db-2", "db-3"]), Some(0));
+//! ```
+use crate::feistel::mix64;
+
+/// The high 64 bits of `id` xored into its low 64 bits
+pub fn fold(id: u128) -> u64 {
+    (id >> 64) as u64 ^ id as u64
+}
+
+/// Jump consistent hash of `fold(id)` into `0..buckets`, `None` for zero buckets
+pub fn jump_hash(id: u128, buckets: u32) -> Option<u32> {
+    if buckets == 0 {
+        return None;
+    }
+    let mut key = fold(id);
+    let (mut b, mut j) = (-1i64, 0i64);
+    while j < buckets as i64 {
+        b = j;
+        key = key.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
+        j = ((b + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    Some(b as u32)
+}
+
+/// 64 bit FNV-1a of a node name
+pub fn node_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// The rendezvous score of `id` on the node with hash `node_hash`
+pub fn rendezvous_score(id: u128, node_hash: u64) -> u64 {
+    mix64((id >> 64) as u64 ^ mix64(id as u64 ^ node_hash))
+}
+
+/// Index of the node `id` belongs to by rendezvous hashing, `None` without nodes
+pub fn rendezvous<I>(id: u128, nodes: I) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut best: Option<(usize, u64)> = None;
+    for (i, node) in nodes.into_iter().enumerate() {
+        let score = rendezvous_score(id, node_hash(node.as_ref()));
+        match best {
+            Some((_, best_score)) if best_score >= score => {}
+            _ => best = Some((i, score)),
+        }
+    }
+    best.map(|(i, _)| i)
+}
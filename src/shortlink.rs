@@ -0,0 +1,429 @@
+//! The core of a URL shortener: long URLs map to short b62 codes, kept in a pluggable
+//! `LinkStore`, with a minimal HTTP/1.1 handler to redirect and create links.
+//!
+//! Codes come from a `CodeGenerator`, either a `SequenceAllocator` for the shortest
+//! codes or `HashCodes` for fixed length codes which do not reveal how many links exist.
+//!
+//! ```
+//! use rb62::sequence::{MemoryStorage, SequenceAllocator};
+//! use rb62::shortlink::{MemoryStore, Shortener};
+//!
+//! let codes = SequenceAllocator::new(MemoryStorage::with_value(1000), 100).unwrap();
+//! let shortener = Shortener::new(MemoryStore::new(), codes);
+//! let code = shortener.shorten("https://open.spotify.com/track/6GGODyP2LIdbxIfYxy5UbN").unwrap();
+//! assert_eq!(code, "g8");
+//! assert_eq!(shortener.resolve("g8").as_deref(), Some("https://open.spotify.com/track/6GGODyP2LIdbxIfYxy5UbN"));
+//! ```
+//!
+//! The HTTP handler understands `GET /<code>`, answered with a redirect, and `POST /`
+//! with the URL as body, answered with the code:
+//!
+//! ```no_run
+//! # use rb62::sequence::{MemoryStorage, SequenceAllocator};
+//! # use rb62::shortlink::{MemoryStore, Shortener};
+//! # let shortener = Shortener::new(MemoryStore::new(), SequenceAllocator::new(MemoryStorage::new(), 100).unwrap());
+//! let listener = std::net::TcpListener::bind("127.0.0.1:8062").unwrap();
+//! shortener.serve(listener).unwrap(); // curl -d https://spotify.com localhost:8062
+//! ```
+use std::collections::HashMap;
+use std::fmt;
+use std::format;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::time::Duration;
+use std::vec;
+use std::vec::Vec;
+
+use crate::feistel::mix64;
+use crate::get_b62_from_integer;
+use crate::sequence::{SequenceAllocator, SequenceError, Storage};
+use crate::shard::node_hash;
+
+/// Max number of codes tried for one URL before giving up
+pub const MAX_ATTEMPTS: u32 = 16;
+/// Max length of a URL
+pub const MAX_URL_LEN: usize = 8192;
+/// Default read and write timeout of a connection in `Shortener::serve`
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum ShortlinkError {
+    /// Not an absolute http or https URL, too long, or containing whitespace or control chars
+    InvalidUrl,
+    /// No free code was found
+    Exhausted,
+    Storage(io::Error),
+}
+
+impl fmt::Display for ShortlinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortlinkError::InvalidUrl => write!(f, "invalid url"),
+            ShortlinkError::Exhausted => write!(f, "no free short link code"),
+            ShortlinkError::Storage(err) => write!(f, "short link storage error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ShortlinkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ShortlinkError::Storage(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ShortlinkError {
+    fn from(err: io::Error) -> ShortlinkError {
+        ShortlinkError::Storage(err)
+    }
+}
+
+/// Where codes and URLs are kept
+pub trait LinkStore {
+    fn url(&self, code: &str) -> Option<String>;
+
+    /// The code an URL was stored under, so shortening it again gives the same code
+    fn code(&self, url: &str) -> Option<String>;
+
+    /// Store a new link, returns false without storing anything if `code` is taken
+    fn insert(&mut self, code: &str, url: &str) -> io::Result<bool>;
+}
+
+/// Keeps links in memory only
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    urls: HashMap<String, String>,
+    codes: HashMap<String, String>,
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+}
+
+impl LinkStore for MemoryStore {
+    fn url(&self, code: &str) -> Option<String> {
+        self.urls.get(code).cloned()
+    }
+
+    fn code(&self, url: &str) -> Option<String> {
+        self.codes.get(url).cloned()
+    }
+
+    fn insert(&mut self, code: &str, url: &str) -> io::Result<bool> {
+        if self.urls.contains_key(code) {
+            return Ok(false);
+        }
+        self.urls.insert(code.to_string(), url.to_string());
+        self.codes.entry(url.to_string()).or_insert_with(|| code.to_string());
+        Ok(true)
+    }
+}
+
+/// Appends each link as a `<code>\t<url>` line to a file and keeps all of them in memory.
+/// Opening replays the file, and cuts off a last line torn by a crash. A failed insert
+/// cuts off what it wrote, inserting a code or URL `open` would reject fails with
+/// `InvalidInput`.
+#[derive(Debug)]
+pub struct FileStore {
+    file: File,
+    links: MemoryStore,
+}
+
+impl FileStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileStore> {
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+        let mut links = MemoryStore::new();
+        let mut valid_len = 0u64;
+        let mut reader = BufReader::new(&mut file);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            let link = std::str::from_utf8(&line[..line.len() - 1]).ok().and_then(|link| {
+                let (code, url) = link.split_once('\t')?;
+                Some((code, url)).filter(|(code, url)| valid_code(code) && valid_url(url))
+            });
+            let (code, url) = link.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid short link line"))?;
+            links.insert(code, url)?;
+            valid_len += line.len() as u64;
+        }
+        drop(reader);
+        if file.seek(SeekFrom::End(0))? != valid_len {
+            file.set_len(valid_len)?;
+        }
+        Ok(FileStore { file, links })
+    }
+
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
+
+impl LinkStore for FileStore {
+    fn url(&self, code: &str) -> Option<String> {
+        self.links.url(code)
+    }
+
+    fn code(&self, url: &str) -> Option<String> {
+        self.links.code(url)
+    }
+
+    fn insert(&mut self, code: &str, url: &str) -> io::Result<bool> {
+        // anything `open` would reject must not get into the file
+        if !valid_code(code) || !valid_url(url) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid short link"));
+        }
+        if self.links.url(code).is_some() {
+            return Ok(false);
+        }
+        let len = self.file.metadata()?.len();
+        let written = self.file.write_all(format!("{}\t{}\n", code, url).as_bytes()).and_then(|()| self.file.sync_data());
+        if let Err(err) = written {
+            // cut off a partly written line, or the next line would be appended to it
+            let _ = self.file.set_len(len);
+            return Err(err);
+        }
+        self.links.insert(code, url)
+    }
+}
+
+/// Makes codes for URLs
+pub trait CodeGenerator {
+    /// A code for `url`, `attempt` counts up from 0 as long as the codes are taken
+    fn code(&self, url: &str, attempt: u32) -> Result<String, ShortlinkError>;
+}
+
+/// The next value of the sequence as unpadded b62, ignoring the URL
+impl<S: Storage> CodeGenerator for SequenceAllocator<S>
+where
+    S::Error: fmt::Display,
+{
+    fn code(&self, _url: &str, _attempt: u32) -> Result<String, ShortlinkError> {
+        self.next_code().map_err(|err| match err {
+            SequenceError::Storage(err) => ShortlinkError::Storage(io::Error::new(io::ErrorKind::Other, err.to_string())),
+            SequenceError::Exhausted => ShortlinkError::Exhausted,
+        })
+    }
+}
+
+/// Fixed length codes derived from a hash of the URL and the attempt, so a taken code
+/// is resolved by trying the next attempt
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashCodes {
+    len: usize,
+}
+
+impl HashCodes {
+    /// Panics if `len` is not between 1 and 10, 62^10 is the most a u64 hash covers
+    pub fn new(len: usize) -> HashCodes {
+        assert!((1..=10).contains(&len), "hash code length must be 1 to 10");
+        HashCodes { len }
+    }
+}
+
+impl CodeGenerator for HashCodes {
+    fn code(&self, url: &str, attempt: u32) -> Result<String, ShortlinkError> {
+        let hash = mix64(node_hash(url) ^ mix64(attempt as u64));
+        let value = hash as u128 % 62u128.pow(self.len as u32);
+        let b62 = get_b62_from_integer(value);
+        // only ASCII was written
+        Ok(std::str::from_utf8(&b62[22 - self.len..]).unwrap().to_string())
+    }
+}
+
+/// A link store and a code generator, safe to share between threads
+pub struct Shortener<L, G> {
+    store: Mutex<L>,
+    codes: G,
+    timeout: Duration,
+}
+
+impl<L: LinkStore, G: CodeGenerator> Shortener<L, G> {
+    pub fn new(store: L, codes: G) -> Shortener<L, G> {
+        Shortener { store: Mutex::new(store), codes, timeout: DEFAULT_TIMEOUT }
+    }
+
+    /// Read and write timeout of a connection in `serve`, `DEFAULT_TIMEOUT` by default.
+    /// Panics if `timeout` is zero.
+    pub fn timeout(mut self, timeout: Duration) -> Shortener<L, G> {
+        assert!(timeout > Duration::ZERO, "timeout must not be zero");
+        self.timeout = timeout;
+        self
+    }
+
+    /// The code for `url`, the one it already has if it was shortened before
+    pub fn shorten(&self, url: &str) -> Result<String, ShortlinkError> {
+        if !valid_url(url) {
+            return Err(ShortlinkError::InvalidUrl);
+        }
+        let mut store = self.store.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(code) = store.code(url) {
+            return Ok(code);
+        }
+        for attempt in 0..MAX_ATTEMPTS {
+            let code = self.codes.code(url, attempt)?;
+            if store.insert(&code, url)? {
+                return Ok(code);
+            }
+        }
+        Err(ShortlinkError::Exhausted)
+    }
+
+    pub fn resolve(&self, code: &str) -> Option<String> {
+        self.store.lock().unwrap_or_else(|err| err.into_inner()).url(code)
+    }
+
+    /// Answer one HTTP/1.1 request read from `input`, the connection is closed after it
+    pub fn handle<R: BufRead, W: Write>(&self, input: R, output: &mut W) -> io::Result<()> {
+        let (status, location, body) = match read_request(input)? {
+            None => (400, None, "bad request\n".to_string()),
+            Some(request) => match (request.method.as_str(), request.path.strip_prefix('/')) {
+                ("GET", Some(code)) if !code.is_empty() => match self.resolve(code) {
+                    Some(url) => (302, Some(url), String::new()),
+                    None => (404, None, "not found\n".to_string()),
+                },
+                ("POST", Some("")) => match String::from_utf8(request.body).map(|url| self.shorten(url.trim())) {
+                    Ok(Ok(code)) => (201, Some(format!("/{}", code)), format!("{}\n", code)),
+                    Ok(Err(ShortlinkError::InvalidUrl)) | Err(_) => (400, None, "invalid url\n".to_string()),
+                    Ok(Err(err)) => (500, None, format!("{}\n", err)),
+                },
+                ("GET", _) | ("POST", _) => (404, None, "not found\n".to_string()),
+                _ => (405, None, "method not allowed\n".to_string()),
+            },
+        };
+
+        let reason = match status {
+            201 => "Created",
+            302 => "Found",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Internal Server Error",
+        };
+        write!(output, "HTTP/1.1 {} {}\r\n", status, reason)?;
+        if let Some(location) = location {
+            write!(output, "Location: {}\r\n", location)?;
+        }
+        write!(output, "Content-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body)?;
+        output.flush()
+    }
+
+    /// Answer connections one at a time, until accepting fails. A client which is too slow
+    /// to send its request or read the response is dropped after the timeout, so it only
+    /// holds up the others that long.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            // a broken connection only affects its own client
+            let _ = self.handle_connection(stream?);
+        }
+        Ok(())
+    }
+
+    fn handle_connection(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        self.handle(BufReader::new(&stream), &mut &stream)
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// `None` for a malformed request
+fn read_request<R: BufRead>(input: R) -> io::Result<Option<Request>> {
+    let mut input = input.take(MAX_URL_LEN as u64 * 2);
+    let line = match read_line(&mut input)? {
+        Some(line) => line,
+        None => return Ok(None),
+    };
+    let mut parts = line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => (method, target),
+        _ => return Ok(None),
+    };
+    let path = target.split('?').next().unwrap_or_default();
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    loop {
+        let line = match read_line(&mut input)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                match value.trim().parse() {
+                    Ok(len) if len <= MAX_URL_LEN => content_length = len,
+                    _ => return Ok(None),
+                }
+            }
+        }
+    }
+
+    let mut body = vec![0u8; content_length];
+    if input.read_exact(&mut body).is_err() {
+        return Ok(None);
+    }
+    Ok(Some(Request { method, path, body }))
+}
+
+// an absolute http or https URL with a host, so redirects can not go to `javascript:`
+// or protocol relative URLs
+fn valid_url(url: &str) -> bool {
+    if url.len() > MAX_URL_LEN || url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    let rest = match url.find("://") {
+        Some(i) if url[..i].eq_ignore_ascii_case("http") || url[..i].eq_ignore_ascii_case("https") => &url[i + 3..],
+        _ => return false,
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = match host.rfind(':') {
+        Some(i) if !host[i..].contains(']') => &host[..i],
+        _ => host,
+    };
+    !host.is_empty()
+}
+
+/// `None` at the end of `input` or for a line which is not UTF-8
+fn read_line<R: BufRead>(input: &mut R) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    if input.read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(line).ok())
+}
+
+fn valid_code(code: &str) -> bool {
+    !code.is_empty() && code.len() <= 22 && code.bytes().all(|c| c.is_ascii_alphanumeric())
+}
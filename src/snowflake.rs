@@ -0,0 +1,218 @@
+//! Twitter snowflake style u64 ids: a millisecond timestamp, a worker id and a sequence
+//! number, rendered as 11 char b62 with the alphabet of `get_b62`.
+//!
+//! ```
+//! use rb62::snowflake::{Generator, SnowflakeConfig};
+//!
+//! const CONFIG: SnowflakeConfig = SnowflakeConfig::new(1_288_834_974_657); // Twitter's epoch
+//!
+//! let mut generator = Generator::new(CONFIG, 7, || 1_600_000_000_000u64).unwrap();
+//! let id = generator.next_id().unwrap();
+//! let snowflake = CONFIG.decode(id);
+//! assert_eq!((snowflake.millis, snowflake.worker, snowflake.sequence), (1_600_000_000_000, 7, 0));
+//! assert_eq!(CONFIG.decode_b62(core::str::from_utf8(&CONFIG.get_b62(id)).unwrap()), Some(snowflake));
+//! ```
+use core::fmt;
+
+use crate::base62_char;
+use crate::radix::BASE62;
+
+/// Length of a u64 in b62
+pub const B62_LEN: usize = 11;
+
+/// Source of the current time in milliseconds since the Unix epoch. Closures returning
+/// a `u64` are clocks, which makes tests deterministic.
+pub trait Clock {
+    fn now_millis(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// The system wall clock
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        let since_epoch = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        since_epoch.as_millis() as u64
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnowflakeError {
+    /// The clock returned an earlier time than for the previous id
+    ClockMovedBackwards { last_millis: u64, now_millis: u64 },
+    /// The clock is before the epoch of the config
+    BeforeEpoch,
+    /// The time since the epoch does not fit in the timestamp bits any more
+    TimestampOverflow,
+    /// All sequence numbers of this millisecond are used, try again in the next one
+    SequenceExhausted,
+}
+
+impl fmt::Display for SnowflakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnowflakeError::ClockMovedBackwards { last_millis, now_millis } => {
+                write!(f, "clock moved backwards from {} to {}", last_millis, now_millis)
+            }
+            SnowflakeError::BeforeEpoch => write!(f, "clock is before the snowflake epoch"),
+            SnowflakeError::TimestampOverflow => write!(f, "snowflake timestamp overflow"),
+            SnowflakeError::SequenceExhausted => write!(f, "snowflake sequence exhausted for this millisecond"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnowflakeError {}
+
+/// The fields of a snowflake id
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Snowflake {
+    /// Milliseconds since the Unix epoch
+    pub millis: u64,
+    pub worker: u64,
+    pub sequence: u64,
+}
+
+/// Epoch and bit widths. The top bit is always zero so ids stay positive as i64, the
+/// timestamp gets the bits left after the worker and sequence bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SnowflakeConfig {
+    epoch_millis: u64,
+    worker_bits: u32,
+    sequence_bits: u32,
+}
+
+impl SnowflakeConfig {
+    /// Twitter's widths, 41 bits of timestamp, 10 bits of worker, 12 bits of sequence
+    pub const fn new(epoch_millis: u64) -> SnowflakeConfig {
+        SnowflakeConfig { epoch_millis, worker_bits: 10, sequence_bits: 12 }
+    }
+
+    /// Panics if fewer than 32 bits would be left for the timestamp
+    pub const fn worker_bits(mut self, bits: u32) -> SnowflakeConfig {
+        assert!(bits + self.sequence_bits <= 31, "timestamp needs at least 32 bits");
+        self.worker_bits = bits;
+        self
+    }
+
+    /// Panics if fewer than 32 bits would be left for the timestamp
+    pub const fn sequence_bits(mut self, bits: u32) -> SnowflakeConfig {
+        assert!(self.worker_bits + bits <= 31, "timestamp needs at least 32 bits");
+        self.sequence_bits = bits;
+        self
+    }
+
+    pub const fn epoch_millis(&self) -> u64 {
+        self.epoch_millis
+    }
+
+    pub const fn timestamp_bits(&self) -> u32 {
+        63 - self.worker_bits - self.sequence_bits
+    }
+
+    pub const fn max_worker(&self) -> u64 {
+        (1 << self.worker_bits) - 1
+    }
+
+    pub const fn max_sequence(&self) -> u64 {
+        (1 << self.sequence_bits) - 1
+    }
+
+    pub fn decode(&self, id: u64) -> Snowflake {
+        Snowflake {
+            millis: (id >> (self.worker_bits + self.sequence_bits)) + self.epoch_millis,
+            worker: (id >> self.sequence_bits) & self.max_worker(),
+            sequence: id & self.max_sequence(),
+        }
+    }
+
+    /// `decode` of an id parsed with `get_u64`
+    pub fn decode_b62(&self, base62: &str) -> Option<Snowflake> {
+        Some(self.decode(get_u64(base62)?))
+    }
+
+    pub fn get_b62(&self, id: u64) -> [u8; B62_LEN] {
+        get_b62_from_u64(id)
+    }
+
+    fn encode(&self, millis: u64, worker: u64, sequence: u64) -> Result<u64, SnowflakeError> {
+        let elapsed = millis.checked_sub(self.epoch_millis).ok_or(SnowflakeError::BeforeEpoch)?;
+        if elapsed >> self.timestamp_bits() != 0 {
+            return Err(SnowflakeError::TimestampOverflow);
+        }
+        Ok(elapsed << (self.worker_bits + self.sequence_bits) | worker << self.sequence_bits | sequence)
+    }
+}
+
+/// Hands out increasing ids for one worker. Not shared between threads, give each
+/// thread its own worker id or put the generator behind a lock.
+#[derive(Clone, Debug)]
+pub struct Generator<C> {
+    config: SnowflakeConfig,
+    worker: u64,
+    clock: C,
+    /// Time of the last id, `None` before the first one
+    last_millis: Option<u64>,
+    sequence: u64,
+}
+
+impl<C: Clock> Generator<C> {
+    /// `None` if the worker id does not fit the worker bits
+    pub fn new(config: SnowflakeConfig, worker: u64, clock: C) -> Option<Generator<C>> {
+        if worker > config.max_worker() {
+            return None;
+        }
+        Some(Generator { config, worker, clock, last_millis: None, sequence: 0 })
+    }
+
+    pub fn config(&self) -> SnowflakeConfig {
+        self.config
+    }
+
+    pub fn next_id(&mut self) -> Result<u64, SnowflakeError> {
+        let now = self.clock.now_millis();
+        let sequence = match self.last_millis {
+            Some(last_millis) if now < last_millis => {
+                return Err(SnowflakeError::ClockMovedBackwards { last_millis, now_millis: now });
+            }
+            Some(last_millis) if now == last_millis => {
+                if self.sequence == self.config.max_sequence() {
+                    return Err(SnowflakeError::SequenceExhausted);
+                }
+                self.sequence + 1
+            }
+            _ => 0,
+        };
+        let id = self.config.encode(now, self.worker, sequence)?;
+        self.last_millis = Some(now);
+        self.sequence = sequence;
+        Ok(id)
+    }
+}
+
+/// Turn a u64 into its 11 char b62 representation
+pub fn get_b62_from_u64(mut value: u64) -> [u8; B62_LEN] {
+    let mut b62 = [b'0'; B62_LEN];
+    for c in b62.iter_mut().rev() {
+        *c = base62_char((value % 62) as u8).unwrap();
+        value /= 62;
+    }
+    b62
+}
+
+/// Parse the 11 char b62 made by `get_b62_from_u64`, `None` if it is above u64::MAX
+pub fn get_u64(base62: &str) -> Option<u64> {
+    if base62.len() != B62_LEN {
+        return None;
+    }
+    base62.bytes().try_fold(0u64, |value, c| value.checked_mul(62)?.checked_add(BASE62.digit_val(c)? as u64))
+}
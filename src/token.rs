@@ -0,0 +1,161 @@
+//! Prefixed secret tokens with a CRC32 checksum, in the style of GitHub tokens.
+//!
+//! A token looks like `rbk_6GGODyP2LIdbxIfYxy5UbN1BnMyx`: a human readable prefix,
+//! a 22 char b62 body holding 128 bits of caller provided entropy, and a 6 char b62
+//! CRC32 checksum of the prefix and body. The prefix makes tokens easy to find for
+//! secret scanners, the checksum lets anyone reject mistyped or made up tokens
+//! without a database lookup.
+use core::fmt;
+
+use crate::{base62_char, get_b62_from_integer, get_integer};
+
+/// Max length of a token prefix
+pub const MAX_PREFIX_LEN: usize = 16;
+/// Length of the b62 body of a token
+pub const BODY_LEN: usize = 22;
+/// Length of the b62 checksum at the end of a token
+pub const CHECKSUM_LEN: usize = 6;
+/// Max length of a whole token, used to size stack buffers
+pub const MAX_TOKEN_LEN: usize = MAX_PREFIX_LEN + BODY_LEN + CHECKSUM_LEN;
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Describes a family of tokens sharing one prefix, e.g. `TokenFormat::new("rbk_")`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TokenFormat {
+    prefix: &'static str,
+}
+
+impl TokenFormat {
+    /// Panics if the prefix is empty, longer than `MAX_PREFIX_LEN`, or contains
+    /// anything other than ASCII letters, digits, `_` and `-`.
+    pub const fn new(prefix: &'static str) -> TokenFormat {
+        let bytes = prefix.as_bytes();
+        assert!(!bytes.is_empty() && bytes.len() <= MAX_PREFIX_LEN, "invalid token prefix length");
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = bytes[i];
+            assert!(c.is_ascii_alphanumeric() || c == b'_' || c == b'-', "invalid token prefix char");
+            i += 1;
+        }
+        TokenFormat { prefix }
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /// Length of every token of this format
+    pub fn token_len(&self) -> usize {
+        self.prefix.len() + BODY_LEN + CHECKSUM_LEN
+    }
+
+    /// Create a token from 16 bytes of caller provided entropy, e.g. from the OS rng
+    pub fn generate(&self, entropy: [u8; 16]) -> Token {
+        Token { format: *self, body: u128::from_be_bytes(entropy) }
+    }
+
+    /// Parse a token string, returns None if the prefix, body or checksum is wrong
+    pub fn parse(&self, token: &str) -> Option<Token> {
+        let rest = token.strip_prefix(self.prefix)?;
+        if rest.len() != BODY_LEN + CHECKSUM_LEN || !rest.is_ascii() {
+            return None;
+        }
+        let (body_str, checksum_str) = rest.split_at(BODY_LEN);
+        let body = get_integer(body_str)?;
+        let parsed = Token { format: *self, body };
+        if checksum_str.as_bytes() != parsed.checksum_chars() {
+            return None;
+        }
+        Some(parsed)
+    }
+
+    /// Check a token string locally, without looking it up anywhere
+    pub fn verify(&self, token: &str) -> bool {
+        self.parse(token).is_some()
+    }
+}
+
+/// A token of some `TokenFormat`. The `Debug` output only shows the prefix, neither the
+/// secret body nor the checksum computed from it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    format: TokenFormat,
+    body: u128,
+}
+
+impl Token {
+    pub fn format(&self) -> TokenFormat {
+        self.format
+    }
+
+    /// The secret 128 bits of the token
+    pub fn body(&self) -> u128 {
+        self.body
+    }
+
+    /// CRC32 (IEEE) of the prefix followed by the b62 body
+    pub fn checksum(&self) -> u32 {
+        let body = get_b62_from_integer(self.body);
+        crc32(crc32(!0, self.format.prefix.as_bytes()), &body) ^ !0
+    }
+
+    /// Write the full token into `buf` and return it as a str
+    pub fn encode<'a>(&self, buf: &'a mut [u8; MAX_TOKEN_LEN]) -> &'a str {
+        let prefix = self.format.prefix.as_bytes();
+        let body_start = prefix.len();
+        let checksum_start = body_start + BODY_LEN;
+        let end = checksum_start + CHECKSUM_LEN;
+        buf[..body_start].copy_from_slice(prefix);
+        buf[body_start..checksum_start].copy_from_slice(&get_b62_from_integer(self.body));
+        buf[checksum_start..end].copy_from_slice(&self.checksum_chars());
+        // only ASCII was written
+        core::str::from_utf8(&buf[..end]).unwrap()
+    }
+
+    fn checksum_chars(&self) -> [u8; CHECKSUM_LEN] {
+        let mut checksum = self.checksum();
+        let mut chars = [b'0'; CHECKSUM_LEN];
+        for c in chars.iter_mut().rev() {
+            *c = base62_char((checksum % 62) as u8).unwrap();
+            checksum /= 62;
+        }
+        chars
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; MAX_TOKEN_LEN];
+        f.write_str(self.encode(&mut buf))
+    }
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the checksum is computed from the body, so it is redacted as well
+        f.debug_struct("Token").field("prefix", &self.format.prefix).field("secret", &"<redacted>").finish()
+    }
+}
+
+/// Update a CRC32 (IEEE) with `bytes`, start with `!0` and invert the result when done
+pub(crate) fn crc32(mut crc: u32, bytes: &[u8]) -> u32 {
+    for b in bytes {
+        crc = CRC32_TABLE[((crc ^ *b as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
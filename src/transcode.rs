@@ -0,0 +1,181 @@
+//! Line by line conversion of id files, for batch jobs reading ids mixed with junk.
+//!
+//! ```
+//! use rb62::transcode::{InvalidLinePolicy, Representation, Transcoder};
+//!
+//! let input = &b"6GGODyP2LIdbxIfYxy5UbN\nnot an id\n"[..];
+//! let mut output = Vec::new();
+//! let stats = Transcoder::new(input, Representation::Base62, Representation::Hex)
+//!     .policy(InvalidLinePolicy::Skip)
+//!     .run(&mut output)
+//!     .unwrap();
+//! assert_eq!(output, b"dbc3d5ebe344484da3e2448712a02213\n");
+//! assert_eq!((stats.converted, stats.skipped), (1, 1));
+//! ```
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::vec::Vec;
+
+use crate::base64url::{get_base64url_from_integer, get_integer_from_base64url};
+use crate::hex_format::{HexFormat, MAX_HEX_LEN};
+use crate::{get_b62_from_integer, get_integer, get_integer_from_uuid_hex};
+
+/// How an id is written on a line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Representation {
+    /// 22 char b62
+    Base62,
+    /// 32 char hex, written lowercase, read in either case
+    Hex,
+    /// Dashed 8-4-4-4-12 hex, written lowercase, read in either case
+    UuidHex,
+    /// 22 char unpadded base64url
+    Base64Url,
+    /// The u128 in decimal
+    Decimal,
+}
+
+impl Representation {
+    pub fn parse(&self, text: &str) -> Option<u128> {
+        match self {
+            Representation::Base62 => get_integer(text),
+            Representation::Hex => {
+                if text.len() != 32 || text.starts_with('+') {
+                    return None;
+                }
+                u128::from_str_radix(text, 16).ok()
+            }
+            Representation::UuidHex => get_integer_from_uuid_hex(text),
+            Representation::Base64Url => get_integer_from_base64url(text),
+            Representation::Decimal => {
+                if text.starts_with('+') {
+                    return None;
+                }
+                text.parse().ok()
+            }
+        }
+    }
+
+    /// Write the id into `out` without allocating
+    pub fn write<W: Write>(&self, id: u128, out: &mut W) -> io::Result<()> {
+        let mut buf = [0u8; MAX_HEX_LEN];
+        match self {
+            Representation::Base62 => out.write_all(&get_b62_from_integer(id)),
+            Representation::Hex => out.write_all(HexFormat::new().format(id, &mut buf).as_bytes()),
+            Representation::UuidHex => out.write_all(HexFormat::new().dashed(true).format(id, &mut buf).as_bytes()),
+            Representation::Base64Url => out.write_all(&get_base64url_from_integer(id)),
+            Representation::Decimal => write!(out, "{}", id),
+        }
+    }
+}
+
+/// What to do with a line that is not a valid id
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InvalidLinePolicy {
+    /// Leave it out of the output
+    Skip,
+    /// Stop with `Error::InvalidLine`
+    Fail,
+    /// Copy it to the output as it is
+    Passthrough,
+}
+
+/// Counts of what happened to the lines so far
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    pub lines: u64,
+    pub converted: u64,
+    pub skipped: u64,
+    pub passed_through: u64,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// An invalid line with `InvalidLinePolicy::Fail`, the line number starts at 1
+    InvalidLine { line_number: u64 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {}", err),
+            Error::InvalidLine { line_number } => write!(f, "line {} is not a valid id", line_number),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::InvalidLine { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+/// Reads ids one per line from a `BufRead` and writes them in another representation.
+/// Whitespace around an id is ignored. The line buffer is reused, so no allocation
+/// happens per line once it has grown to the longest line.
+pub struct Transcoder<R> {
+    reader: R,
+    from: Representation,
+    to: Representation,
+    policy: InvalidLinePolicy,
+    line: Vec<u8>,
+    stats: Stats,
+}
+
+impl<R: BufRead> Transcoder<R> {
+    /// A transcoder which fails on invalid lines
+    pub fn new(reader: R, from: Representation, to: Representation) -> Transcoder<R> {
+        Transcoder { reader, from, to, policy: InvalidLinePolicy::Fail, line: Vec::new(), stats: Stats::default() }
+    }
+
+    pub fn policy(mut self, policy: InvalidLinePolicy) -> Transcoder<R> {
+        self.policy = policy;
+        self
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Transcode the next line, returns false at the end of the input
+    pub fn transcode_line<W: Write>(&mut self, out: &mut W) -> Result<bool, Error> {
+        self.line.clear();
+        if self.reader.read_until(b'\n', &mut self.line)? == 0 {
+            return Ok(false);
+        }
+        self.stats.lines += 1;
+
+        let id = std::str::from_utf8(&self.line).ok().and_then(|line| self.from.parse(line.trim()));
+        match (id, self.policy) {
+            (Some(id), _) => {
+                self.to.write(id, out)?;
+                out.write_all(b"\n")?;
+                self.stats.converted += 1;
+            }
+            (None, InvalidLinePolicy::Skip) => self.stats.skipped += 1,
+            (None, InvalidLinePolicy::Passthrough) => {
+                out.write_all(&self.line)?;
+                self.stats.passed_through += 1;
+            }
+            (None, InvalidLinePolicy::Fail) => return Err(Error::InvalidLine { line_number: self.stats.lines }),
+        }
+        Ok(true)
+    }
+
+    /// Transcode all remaining lines and flush the output
+    pub fn run<W: Write>(&mut self, mut out: W) -> Result<Stats, Error> {
+        while self.transcode_line(&mut out)? {}
+        out.flush()?;
+        Ok(self.stats)
+    }
+}
@@ -0,0 +1,200 @@
+//! Spotify URIs, including the legacy user playlist, user collection and local file
+//! forms which still show up in logs and exports.
+//!
+//! ```
+//! use rb62::uri::SpotifyUri;
+//!
+//! let uri: SpotifyUri = "spotify:user:j%C3%B6rg:playlist:6GGODyP2LIdbxIfYxy5UbN".parse().unwrap();
+//! assert_eq!(uri.user(), Some("jörg"));
+//! assert_eq!(uri.normalize().to_string(), "spotify:playlist:6GGODyP2LIdbxIfYxy5UbN");
+//! ```
+//!
+//! User names and local file fields are percent encoded, with `+` for space. Parsing
+//! decodes them, displaying encodes them again.
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::id::{Album, Artist, Episode, Id, Playlist, Show, Track};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SpotifyUri {
+    Track(Id<Track>),
+    Album(Id<Album>),
+    Artist(Id<Artist>),
+    Playlist(Id<Playlist>),
+    Show(Id<Show>),
+    Episode(Id<Episode>),
+    /// `spotify:user:<user>:playlist:<id>`, the legacy form of a playlist
+    UserPlaylist { user: String, playlist: Id<Playlist> },
+    /// `spotify:user:<user>:collection`
+    UserCollection { user: String },
+    /// `spotify:local:<artist>:<album>:<title>:<seconds>`
+    Local(LocalFile),
+}
+
+/// A file on the user's device, any of the text fields may be empty
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LocalFile {
+    pub artist: String,
+    pub album: String,
+    pub title: String,
+    pub duration_secs: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UriError {
+    /// Does not start with `spotify:`
+    NotSpotify,
+    /// Unknown type after `spotify:`, or the wrong number of parts for the type
+    UnknownForm,
+    /// The id is not a valid 22 char b62
+    InvalidId,
+    /// Bad `%` escape, or the decoded bytes are not UTF-8
+    InvalidEncoding,
+    /// The duration of a local file is not a number of seconds
+    InvalidDuration,
+}
+
+impl fmt::Display for UriError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UriError::NotSpotify => write!(f, "not a spotify uri"),
+            UriError::UnknownForm => write!(f, "unknown spotify uri form"),
+            UriError::InvalidId => write!(f, "invalid b62 id in spotify uri"),
+            UriError::InvalidEncoding => write!(f, "invalid percent encoding in spotify uri"),
+            UriError::InvalidDuration => write!(f, "invalid local file duration in spotify uri"),
+        }
+    }
+}
+
+impl SpotifyUri {
+    /// The user of the legacy forms
+    pub fn user(&self) -> Option<&str> {
+        match self {
+            SpotifyUri::UserPlaylist { user, .. } | SpotifyUri::UserCollection { user } => Some(user),
+            _ => None,
+        }
+    }
+
+    /// Turn a legacy user playlist into the modern `spotify:playlist:<id>` form, other
+    /// forms stay as they are
+    pub fn normalize(self) -> SpotifyUri {
+        match self {
+            SpotifyUri::UserPlaylist { playlist, .. } => SpotifyUri::Playlist(playlist),
+            uri => uri,
+        }
+    }
+}
+
+impl FromStr for SpotifyUri {
+    type Err = UriError;
+
+    fn from_str(uri: &str) -> Result<SpotifyUri, UriError> {
+        let rest = uri.strip_prefix("spotify:").ok_or(UriError::NotSpotify)?;
+        let parts: Vec<&str> = rest.split(':').collect();
+        let uri = match parts.as_slice() {
+            ["track", id] => SpotifyUri::Track(parse_id(id)?),
+            ["album", id] => SpotifyUri::Album(parse_id(id)?),
+            ["artist", id] => SpotifyUri::Artist(parse_id(id)?),
+            ["playlist", id] => SpotifyUri::Playlist(parse_id(id)?),
+            ["show", id] => SpotifyUri::Show(parse_id(id)?),
+            ["episode", id] => SpotifyUri::Episode(parse_id(id)?),
+            ["user", user, "playlist", id] => SpotifyUri::UserPlaylist {
+                user: percent_decode(user).ok_or(UriError::InvalidEncoding)?,
+                playlist: parse_id(id)?,
+            },
+            ["user", user, "collection"] => SpotifyUri::UserCollection {
+                user: percent_decode(user).ok_or(UriError::InvalidEncoding)?,
+            },
+            ["local", artist, album, title, duration] => SpotifyUri::Local(LocalFile {
+                artist: percent_decode(artist).ok_or(UriError::InvalidEncoding)?,
+                album: percent_decode(album).ok_or(UriError::InvalidEncoding)?,
+                title: percent_decode(title).ok_or(UriError::InvalidEncoding)?,
+                duration_secs: parse_duration(duration)?,
+            }),
+            _ => return Err(UriError::UnknownForm),
+        };
+        Ok(uri)
+    }
+}
+
+impl fmt::Display for SpotifyUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpotifyUri::Track(id) => write!(f, "{}", id.uri()),
+            SpotifyUri::Album(id) => write!(f, "{}", id.uri()),
+            SpotifyUri::Artist(id) => write!(f, "{}", id.uri()),
+            SpotifyUri::Playlist(id) => write!(f, "{}", id.uri()),
+            SpotifyUri::Show(id) => write!(f, "{}", id.uri()),
+            SpotifyUri::Episode(id) => write!(f, "{}", id.uri()),
+            SpotifyUri::UserPlaylist { user, playlist } => {
+                write!(f, "spotify:user:{}:playlist:{}", PercentEncode(user), playlist)
+            }
+            SpotifyUri::UserCollection { user } => write!(f, "spotify:user:{}:collection", PercentEncode(user)),
+            SpotifyUri::Local(local) => write!(
+                f,
+                "spotify:local:{}:{}:{}:{}",
+                PercentEncode(&local.artist),
+                PercentEncode(&local.album),
+                PercentEncode(&local.title),
+                local.duration_secs
+            ),
+        }
+    }
+}
+
+/// Decode `%XX` escapes and `+` as space, None if an escape is malformed or the
+/// result is not UTF-8
+pub fn percent_decode(encoded: &str) -> Option<String> {
+    let encoded = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        match encoded[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = encoded.get(i + 1..i + 3)?;
+                let high = (hex[0] as char).to_digit(16)?;
+                let low = (hex[1] as char).to_digit(16)?;
+                decoded.push((high << 4 | low) as u8);
+                i += 2;
+            }
+            c => decoded.push(c),
+        }
+        i += 1;
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Displays a str percent encoded, with `+` for space. ASCII letters, digits and
+/// `-._~` are kept as they are.
+pub struct PercentEncode<'a>(pub &'a str);
+
+impl fmt::Display for PercentEncode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.bytes() {
+            match byte {
+                b' ' => f.write_str("+")?,
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    // ASCII, so a char of its own
+                    write!(f, "{}", byte as char)?
+                }
+                _ => write!(f, "%{:02X}", byte)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_id<K: crate::id::Kind>(id: &str) -> Result<Id<K>, UriError> {
+    Id::from_b62(id).ok_or(UriError::InvalidId)
+}
+
+fn parse_duration(duration: &str) -> Result<u32, UriError> {
+    if duration.is_empty() || !duration.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(UriError::InvalidDuration);
+    }
+    duration.parse().map_err(|_| UriError::InvalidDuration)
+}
@@ -0,0 +1,61 @@
+use rb62::base64url::{
+    classify, get_b62_from_base64url, get_base64url, get_base64url_from_integer,
+    get_integer_from_base64url, Classification,
+};
+use std::str;
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+#[test]
+fn base64url_works() {
+    assert_eq!(str::from_utf8(&get_base64url_from_integer(VALUE)).unwrap(), "28PV6-NESE2j4kSHEqAiEw");
+    assert_eq!(str::from_utf8(&get_base64url_from_integer(0)).unwrap(), "AAAAAAAAAAAAAAAAAAAAAA");
+    assert_eq!(str::from_utf8(&get_base64url_from_integer(u128::MAX)).unwrap(), "_____________________w");
+    assert_eq!(get_integer_from_base64url("28PV6-NESE2j4kSHEqAiEw"), Some(VALUE));
+    assert_eq!(get_integer_from_base64url("_____________________w"), Some(u128::MAX));
+    assert_eq!(get_integer_from_base64url("AAAAAAAAAAAAAAAAAACg8A"), Some(0xa0f0));
+}
+
+#[test]
+fn base64url_transcoding_works() {
+    let base64url = get_base64url("6GGODyP2LIdbxIfYxy5UbN").expect("get_base64url can parse b62");
+    assert_eq!(str::from_utf8(&base64url).unwrap(), "28PV6-NESE2j4kSHEqAiEw");
+    let b62 = get_b62_from_base64url("28PV6-NESE2j4kSHEqAiEw").expect("get_b62_from_base64url can parse base64url");
+    assert_eq!(str::from_utf8(&b62).unwrap(), "6GGODyP2LIdbxIfYxy5UbN");
+}
+
+#[test]
+fn base64url_should_return_none_when_input_invalid() {
+    let invalid_inputs = [
+        "28PV6-NESE2j4kSHEqAiE",    // Too short
+        "28PV6-NESE2j4kSHEqAiEw==", // Padding is not allowed
+        "28PV6+NESE2j4kSHEqAiEw",   // Standard base64 alphabet (+)
+        "28PV6-NESE2j4kSHEqAiEx",   // Unused bits of the last char are set
+    ];
+    for invalid in &invalid_inputs {
+        assert_eq!(get_integer_from_base64url(invalid), None, "{} should not parse", invalid);
+        assert_eq!(get_b62_from_base64url(invalid), None);
+    }
+}
+
+#[test]
+fn classify_works() {
+    assert_eq!(classify("6GGODyP2LIdbxIfYxy5UbN"), Classification::Base62(VALUE));
+    assert_eq!(classify("28PV6-NESE2j4kSHEqAiEw"), Classification::Base64Url(VALUE));
+    assert_eq!(classify("AAAAAAAAAAAAAAAAAAAAAA"), Classification::Base64Url(0)); // too large for b62
+    assert_eq!(
+        classify("000000000000000000000A"),
+        Classification::Ambiguous { base62: 36, base64url: 0xd34d34d34d34d34d34d34d34d34d34d0 }
+    );
+    assert_eq!(classify("000000000000000000000+"), Classification::Invalid);
+    assert_eq!(classify("short"), Classification::Invalid);
+}
+
+#[test]
+fn classification_display_works() {
+    assert_eq!(
+        classify("000000000000000000000A").to_string(),
+        "ambiguous, b62 of 00000000000000000000000000000024 or base64url of d34d34d34d34d34d34d34d34d34d34d0"
+    );
+    assert_eq!(classify("short").to_string(), "neither valid b62 nor valid base64url");
+}
@@ -22,6 +22,10 @@ extern "C" {
 
 use test::Bencher;
 use rb62::{get_integer, get_b62, get_hex};
+use rb62::hash::IdHashBuilder;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
 
 #[cfg(feature = "bench_cpp")]
 use std::ffi::{CStr, CString};
@@ -163,6 +167,52 @@ fn bench_single_operation_rust_b62_to_hex_not_using_format(b: &mut Bencher) {
     });
 }
 
+fn hash_bench_ids() -> Vec<u128> {
+    // splitmix64 pairs, round tripped through b62 like ids read from requests
+    let mut state = 0u64;
+    let mut next = || {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+    (0..10_000)
+        .map(|_| {
+            let b62 = rb62::get_b62_from_integer((next() as u128) << 64 | next() as u128);
+            get_integer(str::from_utf8(&b62).unwrap()).unwrap()
+        })
+        .collect()
+}
+
+fn bench_hash_map<S: BuildHasher + Clone>(b: &mut Bencher, hash_builder: S) {
+    let ids = hash_bench_ids();
+    b.iter(|| {
+        let mut map = HashMap::with_capacity_and_hasher(ids.len(), hash_builder.clone());
+        for (i, id) in ids.iter().enumerate() {
+            map.insert(*id, i);
+        }
+        for (i, id) in ids.iter().enumerate() {
+            assert_eq!(map.get(id), Some(&i));
+        }
+    });
+}
+
+#[bench]
+fn bench_hash_map_default_hasher(b: &mut Bencher) {
+    bench_hash_map(b, RandomState::new());
+}
+
+#[bench]
+fn bench_hash_map_id_hash_builder(b: &mut Bencher) {
+    bench_hash_map(b, IdHashBuilder::new());
+}
+
+#[bench]
+fn bench_hash_map_id_hash_builder_hardened(b: &mut Bencher) {
+    bench_hash_map(b, IdHashBuilder::hardened([0x9e3779b97f4a7c15, 0xbf58476d1ce4e5b9]));
+}
+
 #[cfg(feature = "bench_cpp")]
 #[test]
 fn cpp_convert_to_base62_works_for_all() {
@@ -212,7 +262,7 @@ fn cpp_convert_from_base62_should_return_false_when_input_invalid() {
     }
 }
 
-const TEST_DATA: &'static [Base62TestData] = &[
+const TEST_DATA: &[Base62TestData] = &[
     // Base62TestData("0000000000000000000001", "00000000000000000000000000000001"),
     Base62TestData("0000000000000000000002", "00000000000000000000000000000002"),
     Base62TestData("0000000000000000000004", "00000000000000000000000000000004"),
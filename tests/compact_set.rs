@@ -0,0 +1,146 @@
+use rb62::compact_set::{read_varint, write_varint, CompactSet, CompactSetError, MAX_VARINT_LEN};
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+// The ids 1, 5, 6 and VALUE in blocks of 2
+const SET: [u8; 96] = [
+    b'R', b'B', b'6', b'S', 1, 0, 0, 0, 0, 0, 0, 2, // header
+    4,    // 5 - 1
+    0x8d, 0xc4, 0x80, 0x95, 0xf1, 0x90, 0x91, 0xf1, 0xa3, 0x9b, 0xa1, 0xa2, 0xb4, 0xfc, 0xfa, 0xea, 0xc3, 0xb7, 0x03, // VALUE - 6
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 12, // block 0
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 13, // block 1
+    0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 2, // footer
+];
+
+#[test]
+fn varint_works() {
+    let values: [(u128, usize); 5] = [(0, 1), (127, 1), (128, 2), (VALUE, 19), (u128::MAX, 19)];
+    for (value, len) in values.iter() {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        assert_eq!(write_varint(*value, &mut buf), *len);
+        assert_eq!(read_varint(&buf), Some((*value, *len)));
+    }
+}
+
+#[test]
+fn read_varint_rejects_invalid_input() {
+    let mut too_big = [0xffu8; MAX_VARINT_LEN];
+    too_big[MAX_VARINT_LEN - 1] = 0x04;
+    let invalid: [&[u8]; 5] = [
+        &[],           // empty
+        &[0x80],       // truncated
+        &[0x80, 0x00], // overlong
+        &too_big,      // more than 128 bits
+        &[0xff; 20],   // too long
+    ];
+    for bytes in invalid.iter() {
+        assert_eq!(read_varint(bytes), None, "{:?}", bytes);
+    }
+}
+
+#[test]
+fn compact_set_parse_works() {
+    let set = CompactSet::parse(&SET).unwrap();
+    assert_eq!(set.len(), 4);
+    assert_eq!(set.block_len(), 2);
+    assert_eq!(set.iter().collect::<Vec<_>>(), [1, 5, 6, VALUE]);
+    for id in &[1, 5, 6, VALUE] {
+        assert!(set.contains(*id));
+    }
+    for id in &[0, 2, 7, VALUE - 1, VALUE + 1, u128::MAX] {
+        assert!(!set.contains(*id));
+    }
+}
+
+#[test]
+fn compact_set_parse_empty_works() {
+    let empty = [b'R', b'B', b'6', b'S', 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let set = CompactSet::parse(&empty).unwrap();
+    assert!(set.is_empty());
+    assert_eq!(set.iter().next(), None);
+    assert!(!set.contains(0));
+}
+
+#[test]
+fn compact_set_parse_rejects_invalid_input() {
+    let patched = |i: usize, b: u8| {
+        let mut set = SET.to_vec();
+        set[i] = b;
+        set
+    };
+    let invalid: [(Vec<u8>, CompactSetError); 9] = [
+        (SET[..27].to_vec(), CompactSetError::BadMagic), // too short
+        (patched(3, b'L'), CompactSetError::BadMagic),
+        (patched(4, 2), CompactSetError::UnsupportedVersion(2)),
+        (patched(11, 0), CompactSetError::BadHeader),         // zero block length
+        (patched(SET.len() - 1, 3), CompactSetError::Corrupt), // block count does not match
+        (patched(12, 0), CompactSetError::Corrupt),           // zero delta
+        (patched(13, 0x0d), CompactSetError::Corrupt),        // varint shorter than the block
+        (patched(71, 0), CompactSetError::Corrupt),           // index out of order
+        (patched(79, 12), CompactSetError::Corrupt),          // block offset mismatch
+    ];
+    for (bytes, err) in invalid.iter() {
+        assert_eq!(CompactSet::parse(bytes), Err(*err));
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_io {
+    use std::io::ErrorKind;
+
+    use rb62::compact_set::{from_text, to_text, CompactSet, Encoder, DEFAULT_BLOCK_LEN};
+
+    use super::{SET, VALUE};
+
+    #[test]
+    fn encoder_works() {
+        let mut encoder = Encoder::new(Vec::new(), 2).unwrap();
+        for id in &[1, 5, 6, VALUE] {
+            encoder.push(*id).unwrap();
+        }
+        assert_eq!(encoder.finish().unwrap(), SET);
+    }
+
+    #[test]
+    fn encoder_rejects_invalid_input() {
+        assert_eq!(Encoder::new(Vec::new(), 0).err().unwrap().kind(), ErrorKind::InvalidInput);
+        let mut encoder = Encoder::new(Vec::new(), 2).unwrap();
+        encoder.push(5).unwrap();
+        assert_eq!(encoder.push(5).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(encoder.push(4).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn compact_set_is_smaller_than_id_list_for_dense_sets() {
+        let ids: Vec<u128> = (0..10_000u128).map(|i| VALUE + i * 1000).collect();
+        let mut encoder = Encoder::new(Vec::new(), DEFAULT_BLOCK_LEN).unwrap();
+        for id in &ids {
+            encoder.push(*id).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        assert!(bytes.len() < rb62::id_list::encoded_len(ids.len()) / 5, "{}", bytes.len());
+
+        let set = CompactSet::parse(&bytes).unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), ids);
+        assert!(ids.iter().all(|id| set.contains(*id)));
+        assert!(!set.contains(VALUE + 1));
+    }
+
+    #[test]
+    fn text_round_trip_works() {
+        let text = "6GGODyP2LIdbxIfYxy5UbN\n0000000000000000000006\n\n0000000000000000000001\n0000000000000000000005\n0000000000000000000001\n";
+        let mut bytes = Vec::new();
+        assert_eq!(from_text(text.as_bytes(), &mut bytes, 2).unwrap(), 4);
+        assert_eq!(bytes, SET);
+
+        let mut output = Vec::new();
+        to_text(&CompactSet::parse(&bytes).unwrap(), &mut output).unwrap();
+        assert_eq!(
+            output,
+            &b"0000000000000000000001\n0000000000000000000005\n0000000000000000000006\n6GGODyP2LIdbxIfYxy5UbN\n"[..]
+        );
+
+        let err = from_text(&b"nope\n"[..], Vec::new(), 2).unwrap_err();
+        assert_eq!(err.to_string(), "line 1 is not a valid id");
+    }
+}
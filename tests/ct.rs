@@ -0,0 +1,133 @@
+use rb62::{ct, get_b62, get_b62_from_integer, get_hex, get_integer};
+use std::time::Instant;
+
+// xorshift64*, good enough to make test inputs
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        (self.next_u64() as u128) << 64 | self.next_u64() as u128
+    }
+}
+
+#[test]
+fn ct_functions_match_normal_functions() {
+    let mut rng = Rng(0x1234_5678);
+    let mut values = vec![0, 1, 61, 62, u128::MAX, u128::MAX - 1, 0xdbc3d5ebe344484da3e2448712a02213];
+    values.extend((0..1000).map(|_| rng.next_u128()));
+
+    for value in values {
+        let b62 = get_b62_from_integer(value);
+        assert_eq!(ct::get_b62_from_integer(value), b62);
+        let b62 = std::str::from_utf8(&b62).unwrap();
+        assert_eq!(ct::get_integer(b62), get_integer(b62));
+        assert_eq!(ct::get_hex(b62), get_hex(b62));
+
+        let hex = format!("{:032x}", value);
+        assert_eq!(ct::get_b62(&hex), get_b62(&hex));
+        assert_eq!(ct::get_b62(&hex.to_uppercase()), get_b62(&hex));
+    }
+}
+
+#[test]
+fn ct_get_integer_should_return_none_when_input_invalid() {
+    let invalid_inputs = [
+        "000000000000000000000+",  // Invalid characters (+)
+        "000000000000000000001",   // String is too short
+        "7N42dgm5tFLK9N8MT7fHC8",  // Too large (max is 7N42dgm5tFLK9N8MT7fHC7)
+        "7N42dgm5tFLK9N8MT7fHD0",  // Too large, differs earlier
+        "ZZZZZZZZZZZZZZZZZZZZZZ",  // Definately too large to fit in 128 bits
+        "/00000000000000000000:",  // Chars right next to the valid ranges
+        "@00000000000000000000[",
+        "`00000000000000000000{",
+    ];
+
+    for invalid in &invalid_inputs {
+        assert_eq!(ct::get_integer(invalid), None, "{} should not parse", invalid);
+    }
+    assert_eq!(ct::get_integer("7N42dgm5tFLK9N8MT7fHC7"), Some(u128::MAX));
+    assert_eq!(ct::get_integer("7N42dgm5tFLK9N8MT7fHB9"), get_integer("7N42dgm5tFLK9N8MT7fHB9"));
+}
+
+#[test]
+fn ct_get_b62_should_return_none_when_input_invalid() {
+    let invalid_inputs = [
+        "0000000000000000000000000000000+",
+        "g0000000000000000000000000000001",
+        "ffffffffffffffffffffffffffffffff1",
+        "1", // get_b62 accepts short hex, ct::get_b62 needs all 32 chars
+    ];
+
+    for invalid in &invalid_inputs {
+        assert_eq!(ct::get_b62(invalid), None, "{} should not parse", invalid);
+    }
+}
+
+// Welch's t-test between the timings of two classes of input, like dudect does.
+// A |t| above 4.5 is usually taken as evidence of a timing leak.
+fn welch_t(class_a: &[f64], class_b: &[f64]) -> f64 {
+    fn mean_var(xs: &[f64]) -> (f64, f64) {
+        let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+        let var = xs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (xs.len() - 1) as f64;
+        (mean, var)
+    }
+    let (mean_a, var_a) = mean_var(class_a);
+    let (mean_b, var_b) = mean_var(class_b);
+    (mean_a - mean_b) / (var_a / class_a.len() as f64 + var_b / class_b.len() as f64).sqrt()
+}
+
+// Time `f` on a fixed input (class 0) against random inputs (class 1), interleaved in
+// random order, dropping the slowest 10% of samples to cut down on noise. Both classes
+// are laid out the same way in memory so caching does not tell them apart.
+fn timing_t_value<T: Clone, F: Fn(&T)>(fixed: T, random: Vec<T>, f: F) -> f64 {
+    const BATCH: usize = 32;
+    let mut rng = Rng(0xdead_beef);
+    let inputs: Vec<(usize, T)> = random.into_iter()
+        .map(|input| match rng.next_u64() & 1 {
+            0 => (0, fixed.clone()),
+            _ => (1, input),
+        })
+        .collect();
+
+    let mut classes = [Vec::new(), Vec::new()];
+    for (class, input) in inputs.iter() {
+        let start = Instant::now();
+        for _ in 0..BATCH {
+            f(std::hint::black_box(input));
+        }
+        classes[*class].push(start.elapsed().as_nanos() as f64);
+    }
+    for samples in classes.iter_mut() {
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        samples.truncate(samples.len() * 9 / 10);
+    }
+    welch_t(&classes[0], &classes[1])
+}
+
+#[test]
+#[ignore] // timing based, run locally with `cargo test --release --test ct -- --ignored`
+fn ct_timing_does_not_depend_on_input() {
+    const SAMPLES: usize = 200_000;
+    let mut rng = Rng(0x5eed);
+    let values: Vec<u128> = (0..SAMPLES).map(|_| rng.next_u128()).collect();
+    let b62s: Vec<String> = values.iter()
+        .map(|v| String::from_utf8(get_b62_from_integer(*v).to_vec()).unwrap())
+        .collect();
+
+    let t = timing_t_value(String::from("0000000000000000000000"), b62s, |s| {
+        std::hint::black_box(ct::get_integer(s));
+    });
+    assert!(t.abs() < 4.5, "ct::get_integer timing depends on input, t = {}", t);
+
+    let t = timing_t_value(0u128, values, |v| {
+        std::hint::black_box(ct::get_b62_from_integer(*v));
+    });
+    assert!(t.abs() < 4.5, "ct::get_b62_from_integer timing depends on input, t = {}", t);
+}
@@ -0,0 +1,12 @@
+#![cfg(feature = "derive")]
+use rb62::Base62;
+
+#[derive(Base62, Clone, Copy, PartialEq, Eq)]
+struct TrackId(u128);
+
+#[test]
+fn derive_is_reexported() {
+    let id: TrackId = "6GGODyP2LIdbxIfYxy5UbN".parse().unwrap();
+    assert!(id == TrackId(0xdbc3d5ebe344484da3e2448712a02213));
+    assert_eq!(id.to_string(), "6GGODyP2LIdbxIfYxy5UbN");
+}
@@ -0,0 +1,63 @@
+use rb62::feistel::Feistel;
+use std::str;
+
+const KEY: u128 = 0x0123456789abcdef_fedcba9876543210;
+
+// (id, obfuscated id, b62 of the obfuscated id)
+const TEST_VECTORS: &[(u128, u128, &str)] = &[
+    (0, 0xd7ec3b1b81ce050aba24773ae4d98bc6, "6zrj6pBcXVDplIsUwpUdYW"),
+    (1, 0xd27e1defb9d24b1e3a5311b35938bc0a, "6pc1vi6aTgyFwR6N9PEPtE"),
+    (2, 0x9d863b853c2d73237fba12110e037b7b, "4Nf8Ff7iLyis5c0pmc5CfF"),
+    (1000, 0xa75b451a40e8d01be46a624662af79d3, "55NqBoiR1Yjcd0LNmjDzub"),
+    (u128::MAX, 0x11182f5eb93dcbece24c5865cd35dc9f, "0wfVaKLNiaO5cqz6lmJpsr"),
+];
+
+#[test]
+fn feistel_test_vectors_work() {
+    let feistel = Feistel::new(KEY);
+    for (id, obfuscated, b62) in TEST_VECTORS {
+        assert_eq!(feistel.obfuscate(*id), *obfuscated);
+        assert_eq!(feistel.deobfuscate(*obfuscated), *id);
+        assert_eq!(str::from_utf8(&feistel.get_b62(*id)).unwrap(), *b62);
+        assert_eq!(feistel.get_integer(b62), Some(*id));
+    }
+}
+
+#[test]
+fn feistel_different_keys_give_different_ids() {
+    let a = Feistel::new(KEY);
+    let b = Feistel::new(KEY ^ 1);
+    assert_ne!(a.obfuscate(1), b.obfuscate(1));
+    assert_eq!(b.deobfuscate(b.obfuscate(1)), 1);
+}
+
+#[test]
+fn feistel_is_a_permutation_on_small_domains() {
+    for &(bits, rounds) in &[(2, 1), (4, 3), (8, 8), (16, 4), (16, 8)] {
+        let feistel = Feistel::with_params(KEY, rounds, bits);
+        let domain = 1usize << bits;
+        let mut seen = vec![false; domain];
+        for id in 0..domain as u128 {
+            let obfuscated = feistel.obfuscate(id);
+            assert!(obfuscated < domain as u128, "{} bits: {} left the domain", bits, id);
+            assert!(!seen[obfuscated as usize], "{} bits: {} hit twice", bits, obfuscated);
+            seen[obfuscated as usize] = true;
+            assert_eq!(feistel.deobfuscate(obfuscated), id);
+        }
+    }
+}
+
+#[test]
+fn feistel_keeps_bits_above_the_domain() {
+    let feistel = Feistel::with_params(KEY, 8, 64);
+    let id = 0xabcd_0000_0000_0000_0000_0000_0000_0042;
+    let obfuscated = feistel.obfuscate(id);
+    assert_eq!(obfuscated >> 64, id >> 64);
+    assert_eq!(feistel.deobfuscate(obfuscated), id);
+}
+
+#[test]
+#[should_panic]
+fn feistel_rejects_odd_bits() {
+    Feistel::with_params(KEY, 8, 15);
+}
@@ -0,0 +1,81 @@
+use rb62::file_id::FileId;
+use std::str;
+
+const HEX: &str = "ab67616d0000b273e8b066f70c206551210d902b";
+const BYTES: [u8; 20] = [
+    0xab, 0x67, 0x61, 0x6d, 0x00, 0x00, 0xb2, 0x73, 0xe8, 0xb0,
+    0x66, 0xf7, 0x0c, 0x20, 0x65, 0x51, 0x21, 0x0d, 0x90, 0x2b,
+];
+
+#[test]
+fn file_id_hex_works() {
+    let file_id = FileId::from_hex(HEX).expect("file id can be parsed");
+    assert_eq!(file_id.as_bytes(), &BYTES);
+    assert_eq!(str::from_utf8(&file_id.to_hex()).unwrap(), HEX);
+    assert_eq!(file_id.to_string(), HEX);
+    assert_eq!(format!("{:?}", file_id), format!("FileId({})", HEX));
+    assert_eq!(FileId::from_hex(&HEX.to_uppercase()), Some(file_id));
+    assert_eq!(HEX.parse::<FileId>(), Ok(file_id));
+}
+
+#[test]
+fn file_id_bytes_work() {
+    let file_id = FileId::from(BYTES);
+    assert_eq!(file_id, FileId::from_bytes(BYTES));
+    assert_eq!(<[u8; 20]>::from(file_id), BYTES);
+}
+
+#[test]
+fn file_id_b62_works() {
+    let file_id = FileId::from_bytes(BYTES);
+    assert_eq!(str::from_utf8(&file_id.to_b62()).unwrap(), "osj8DrYMOzhxAuwe571gMVg8FwD");
+    assert_eq!(FileId::from_b62("osj8DrYMOzhxAuwe571gMVg8FwD"), Some(file_id));
+
+    let max = FileId::from_bytes([0xff; 20]);
+    assert_eq!(str::from_utf8(&max.to_b62()).unwrap(), "AwGeptL1TMEBFSqZfp4BXWGY80v");
+    assert_eq!(FileId::from_b62("AwGeptL1TMEBFSqZfp4BXWGY80v"), Some(max));
+    assert_eq!(FileId::from_b62("000000000000000000000000000"), Some(FileId::from_bytes([0; 20])));
+}
+
+#[test]
+fn file_id_should_return_none_when_input_invalid() {
+    let invalid_hex = [
+        "ab67616d0000b273e8b066f70c206551210d902",   // Too short
+        "ab67616d0000b273e8b066f70c206551210d902b0", // Too long
+        "ab67616d0000b273e8b066f70c206551210d902g",  // Invalid characters (g)
+        "+b67616d0000b273e8b066f70c206551210d902b",  // Sign is not a hex digit
+    ];
+    for invalid in &invalid_hex {
+        assert_eq!(FileId::from_hex(invalid), None, "{} should not parse", invalid);
+        assert!(invalid.parse::<FileId>().is_err());
+    }
+
+    let invalid_b62 = [
+        "osj8DrYMOzhxAuwe571gMVg8Fw",   // Too short
+        "AwGeptL1TMEBFSqZfp4BXWGY80w",  // Too large (max is AwGeptL1TMEBFSqZfp4BXWGY80v)
+        "ZZZZZZZZZZZZZZZZZZZZZZZZZZZ",  // Definately too large to fit in 160 bits
+        "osj8DrYMOzhxAuwe571gMVg8Fw+",  // Invalid characters (+)
+    ];
+    for invalid in &invalid_b62 {
+        assert_eq!(FileId::from_b62(invalid), None, "{} should not parse", invalid);
+    }
+}
+
+#[test]
+fn file_id_urls_work() {
+    let file_id = FileId::from_bytes(BYTES);
+    let urls = [
+        "https://i.scdn.co/image/ab67616d0000b273e8b066f70c206551210d902b",
+        "http://i.scdn.co/image/ab67616d0000b273e8b066f70c206551210d902b?size=640",
+        "i.scdn.co/image/ab67616d0000b273e8b066f70c206551210d902b/",
+        "https://p.scdn.co/mp3-preview/ab67616d0000b273e8b066f70c206551210d902b#t=10",
+    ];
+    for url in &urls {
+        assert_eq!(FileId::from_url(url), Some(file_id), "{} should have a file id", url);
+    }
+    assert_eq!(FileId::from_url("ab67616d0000b273e8b066f70c206551210d902b"), None);
+    assert_eq!(FileId::from_url("https://i.scdn.co/image/"), None);
+    assert_eq!(FileId::from_url("https://i.scdn.co/image/ab67616d0000b273e8b066f70c206551210d902b/large"), None);
+
+    assert_eq!(file_id.image_url().to_string(), "https://i.scdn.co/image/ab67616d0000b273e8b066f70c206551210d902b");
+}
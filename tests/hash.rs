@@ -0,0 +1,52 @@
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use rb62::hash::IdHashBuilder;
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+fn hash<T: Hash>(builder: &IdHashBuilder, value: T) -> u64 {
+    let mut hasher = builder.build_hasher();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn id_hash_builder_folds_ids() {
+    let builder = IdHashBuilder::new();
+    assert!(!builder.is_hardened());
+    assert_eq!(hash(&builder, VALUE), 0xdbc3d5ebe344484d ^ 0xa3e2448712a02213);
+    assert_eq!(hash(&builder, rb62::id::Id::<rb62::id::Track>::new(VALUE)), hash(&builder, VALUE));
+    assert_ne!(hash(&builder, "6GGODyP2LIdbxIfYxy5UbN"), hash(&builder, "6GGODyP2LIdbxIfYxy5UbM"));
+}
+
+#[test]
+fn id_hash_builder_hardened_works() {
+    const KEY: [u64; 2] = [0x9e37_79b9_7f4a_7c15, 0xbf58_476d_1ce4_e5b9];
+    let builder = IdHashBuilder::hardened(KEY);
+    assert!(builder.is_hardened());
+    assert_eq!(hash(&builder, VALUE), hash(&IdHashBuilder::hardened(KEY), VALUE));
+    assert_ne!(hash(&builder, VALUE), hash(&IdHashBuilder::hardened([KEY[0], KEY[1] ^ 1]), VALUE));
+    assert_ne!(hash(&builder, VALUE), hash(&IdHashBuilder::new(), VALUE));
+
+    // ids with equal halves all fold to 0, the keyed hash has to spread them
+    let folded: std::collections::HashSet<u64> = (0..1000u128).map(|i| hash(&IdHashBuilder::new(), i << 64 | i)).collect();
+    let hardened: std::collections::HashSet<u64> = (0..1000u128).map(|i| hash(&builder, i << 64 | i)).collect();
+    assert_eq!(folded.len(), 1);
+    assert_eq!(hardened.len(), 1000);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn id_map_works() {
+    use rb62::hash::{IdMap, IdSet};
+
+    let mut map: IdMap<&str> = IdMap::default();
+    map.insert(rb62::get_integer("6GGODyP2LIdbxIfYxy5UbN").unwrap(), "track");
+    assert_eq!(map.get(&VALUE), Some(&"track"));
+
+    let mut set = IdSet::with_hasher(IdHashBuilder::random_hardened());
+    assert!(set.insert(VALUE));
+    assert!(!set.insert(VALUE));
+    assert!(set.contains(&VALUE));
+    assert_ne!(IdHashBuilder::random_hardened(), IdHashBuilder::random_hardened());
+}
@@ -0,0 +1,67 @@
+use rb62::get_hex;
+use rb62::hex_format::{HexFormat, MAX_HEX_LEN};
+use std::str;
+
+const B62: &str = "6GGODyP2LIdbxIfYxy5UbN";
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+// (format, expected output)
+fn test_data() -> Vec<(HexFormat, &'static str)> {
+    vec![
+        (HexFormat::new(), "dbc3d5ebe344484da3e2448712a02213"),
+        (HexFormat::new().uppercase(true), "DBC3D5EBE344484DA3E2448712A02213"),
+        (HexFormat::new().prefix(true), "0xdbc3d5ebe344484da3e2448712a02213"),
+        (HexFormat::new().dashed(true), "dbc3d5eb-e344-484d-a3e2-448712a02213"),
+        (HexFormat::new().little_endian(true), "1322a0128744e2a34d4844e3ebd5c3db"),
+        (
+            HexFormat::new().uppercase(true).prefix(true).dashed(true).little_endian(true),
+            "0x1322A012-8744-E2A3-4D48-44E3EBD5C3DB",
+        ),
+    ]
+}
+
+#[test]
+fn hex_format_works() {
+    for (format, expected) in test_data() {
+        let mut buf = [0u8; MAX_HEX_LEN];
+        assert_eq!(format.format(VALUE, &mut buf), expected);
+        assert_eq!(format.hex_len(), expected.len());
+        assert_eq!(format.get_hex(B62, &mut buf), Some(expected));
+        assert_eq!(format.parse(expected), Some(VALUE));
+        assert_eq!(format.get_b62(expected).map(|b62| str::from_utf8(&b62).unwrap().to_owned()),
+                   Some(B62.to_owned()));
+    }
+}
+
+#[test]
+fn hex_format_default_matches_get_hex() {
+    let mut buf = [0u8; MAX_HEX_LEN];
+    let hex = get_hex(B62).unwrap();
+    assert_eq!(HexFormat::default().get_hex(B62, &mut buf).unwrap().as_bytes(), &hex[..]);
+}
+
+#[test]
+fn hex_format_parse_only_accepts_its_own_form() {
+    let data = test_data();
+    for (i, (format, _)) in data.iter().enumerate() {
+        for (j, (_, other)) in data.iter().enumerate() {
+            // byte order can not be told apart from the text, but it gives another value
+            if i != j {
+                assert_ne!(format.parse(other), Some(VALUE), "{:?} should not parse {}", format, other);
+            }
+        }
+    }
+
+    let invalid_inputs = [
+        "dbc3d5ebe344484da3e2448712a0221",    // Too short
+        "dbc3d5ebe344484da3e2448712a022133",  // Too long
+        "dbc3d5ebe344484da3e2448712a0221g",   // Invalid characters (g)
+        "+bc3d5ebe344484da3e2448712a02213",   // Sign is not a hex digit
+        "dbc3d5ebe344484dA3e2448712a02213",   // Mixed case
+    ];
+    for invalid in &invalid_inputs {
+        assert_eq!(HexFormat::new().parse(invalid), None, "{} should not parse", invalid);
+    }
+    assert_eq!(HexFormat::new().prefix(true).parse("0Xdbc3d5ebe344484da3e2448712a02213"), None);
+    assert_eq!(HexFormat::new().dashed(true).parse("dbc3d5eb-e344-484d-a3e2_448712a02213"), None);
+}
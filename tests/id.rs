@@ -0,0 +1,84 @@
+use rb62::id::{Album, Id, Kind, Track};
+
+enum User {}
+
+impl Kind for User {
+    const NAME: &'static str = "user";
+}
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+#[test]
+fn id_parse_and_format_work() {
+    let track: Id<Track> = "6GGODyP2LIdbxIfYxy5UbN".parse().expect("id can be parsed");
+    assert_eq!(track.value(), VALUE);
+    assert_eq!(track, Id::new(VALUE));
+    assert_eq!(&track.to_b62(), b"6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(track.to_string(), "6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(format!("{:?}", track), "Id<track>(6GGODyP2LIdbxIfYxy5UbN)");
+
+    assert_eq!(track.uri().to_string(), "spotify:track:6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!("spotify:track:6GGODyP2LIdbxIfYxy5UbN".parse::<Id<Track>>(), Ok(track));
+    assert_eq!(Id::<Track>::from_uri("spotify:track:6GGODyP2LIdbxIfYxy5UbN"), Some(track));
+}
+
+#[test]
+fn id_without_uri_prefix_works() {
+    let user: Id<User> = "6GGODyP2LIdbxIfYxy5UbN".parse().unwrap();
+    assert_eq!(user.uri().to_string(), "6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(Id::<User>::from_uri("spotify:user:6GGODyP2LIdbxIfYxy5UbN"), None);
+}
+
+#[test]
+fn id_parse_should_fail_when_input_invalid() {
+    let invalid_inputs = [
+        "6GGODyP2LIdbxIfYxy5Ub",                    // Too short
+        "7N42dgm5tFLK9N8MT7fHC8",                   // Too large
+        "spotify:album:6GGODyP2LIdbxIfYxy5UbN",     // Wrong kind
+        "spotify:track:",                           // No id
+    ];
+    for invalid in &invalid_inputs {
+        let err = invalid.parse::<Id<Track>>().unwrap_err();
+        assert_eq!(err.kind(), "track");
+        assert_eq!(err.to_string(), "invalid track id");
+    }
+}
+
+#[test]
+fn id_cast_works() {
+    let track = Id::<Track>::new(VALUE);
+    let album: Id<Album> = track.cast();
+    assert_eq!(album.value(), track.value());
+    assert_eq!(album.uri().to_string(), "spotify:album:6GGODyP2LIdbxIfYxy5UbN");
+}
+
+#[test]
+fn id_ordering_and_hashing_use_the_value() {
+    let mut ids = vec![Id::<Track>::new(3), Id::new(1), Id::new(2), Id::new(1)];
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids, vec![Id::new(1), Id::new(2), Id::new(3)]);
+    let set: std::collections::HashSet<Id<Track>> = ids.into_iter().collect();
+    assert!(set.contains(&Id::new(2)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn id_serde_works() {
+    let track = Id::<Track>::new(VALUE);
+    assert_eq!(serde_json::to_string(&track).unwrap(), r#""6GGODyP2LIdbxIfYxy5UbN""#);
+    assert_eq!(serde_json::from_str::<Id<Track>>(r#""6GGODyP2LIdbxIfYxy5UbN""#).unwrap(), track);
+    assert_eq!(serde_json::from_str::<Id<Track>>(r#""spotify:track:6GGODyP2LIdbxIfYxy5UbN""#).unwrap(), track);
+    let err = serde_json::from_str::<Id<Track>>(r#""spotify:album:6GGODyP2LIdbxIfYxy5UbN""#).unwrap_err();
+    assert!(err.to_string().contains("invalid track id"));
+    assert!(serde_json::from_str::<Id<Track>>("42").is_err());
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn id_uuid_conversion_works() {
+    let track = Id::<Track>::new(VALUE);
+    let uuid: uuid::Uuid = track.into();
+    assert_eq!(uuid.to_string(), "dbc3d5eb-e344-484d-a3e2-448712a02213");
+    assert_eq!(Id::<Track>::from(uuid), track);
+}
@@ -0,0 +1,148 @@
+use rb62::id_list::{encoded_len, IdList, IdListError};
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+// Two sorted ids, 1 and VALUE
+const LIST: [u8; 52] = [
+    b'R', b'B', b'6', b'2', 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, // header
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // 1
+    0xdb, 0xc3, 0xd5, 0xeb, 0xe3, 0x44, 0x48, 0x4d, 0xa3, 0xe2, 0x44, 0x87, 0x12, 0xa0, 0x22, 0x13, // VALUE
+    0xd2, 0x9b, 0x7f, 0x81, // checksum
+];
+
+// Fix up the checksum of a hand edited list
+fn with_checksum(mut bytes: Vec<u8>) -> Vec<u8> {
+    let len = bytes.len();
+    let crc = crc32(&bytes[..len - 4]);
+    bytes[len - 4..].copy_from_slice(&crc.to_be_bytes());
+    bytes
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    crc ^ !0
+}
+
+#[test]
+fn id_list_parse_works() {
+    assert_eq!(crc32(&LIST[..48]), 0xd29b7f81);
+    let list = IdList::parse(&LIST).unwrap();
+    assert_eq!(list.len(), 2);
+    assert!(list.is_sorted());
+    assert_eq!(list.get(0), Some(1));
+    assert_eq!(list.get(1), Some(VALUE));
+    assert_eq!(list.get(2), None);
+    assert_eq!(list.iter().collect::<Vec<_>>(), [1, VALUE]);
+    assert_eq!(list.iter().rev().collect::<Vec<_>>(), [VALUE, 1]);
+    assert!(list.contains(VALUE));
+    assert!(!list.contains(2));
+    assert_eq!(encoded_len(2), LIST.len());
+}
+
+#[test]
+fn id_list_parse_empty_works() {
+    let list = with_checksum([&LIST[..15], &[0, 0, 0, 0, 0][..]].concat());
+    let list = IdList::parse(&list).unwrap();
+    assert!(list.is_empty());
+    assert!(!list.contains(0));
+}
+
+#[test]
+fn id_list_parse_rejects_invalid_input() {
+    let mut unsorted = LIST.to_vec();
+    unsorted[16..48].rotate_left(16);
+    let mut unknown_flag = LIST.to_vec();
+    unknown_flag[5] = 3;
+    let mut reserved = LIST.to_vec();
+    reserved[7] = 1;
+    let mut version = LIST.to_vec();
+    version[4] = 2;
+    let mut corrupted = LIST.to_vec();
+    corrupted[20] ^= 1;
+    let mut huge_count = LIST.to_vec();
+    huge_count[8] = 0xff;
+
+    let invalid: [(&[u8], IdListError); 9] = [
+        (&LIST[..19], IdListError::BadMagic),                          // too short
+        (&with_checksum([b"RB63", &LIST[4..]].concat()), IdListError::BadMagic),
+        (&with_checksum(version), IdListError::UnsupportedVersion(2)),
+        (&with_checksum(unknown_flag), IdListError::BadHeader),
+        (&with_checksum(reserved), IdListError::BadHeader),
+        (&LIST[..36], IdListError::LengthMismatch),                    // truncated record
+        (&with_checksum(huge_count), IdListError::LengthMismatch),     // count overflows
+        (&corrupted, IdListError::ChecksumMismatch),
+        (&with_checksum(unsorted), IdListError::NotSorted),
+    ];
+    for (bytes, err) in invalid.iter() {
+        assert_eq!(IdList::parse(bytes), Err(*err));
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_io {
+    use std::io::ErrorKind;
+
+    use rb62::id_list::{from_text, to_text, IdList, MappedIdList, Writer};
+
+    use super::{LIST, VALUE};
+
+    #[test]
+    fn writer_works() {
+        let mut writer = Writer::new(Vec::new(), 2, true).unwrap();
+        writer.push(1).unwrap();
+        writer.push(VALUE).unwrap();
+        assert_eq!(writer.finish().unwrap(), LIST);
+    }
+
+    #[test]
+    fn writer_rejects_invalid_input() {
+        let mut writer = Writer::new(Vec::new(), 2, true).unwrap();
+        writer.push(VALUE).unwrap();
+        assert_eq!(writer.push(1).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(writer.finish().unwrap_err().to_string(), "id list announced 2 ids but 1 were written");
+
+        let mut writer = Writer::new(Vec::new(), 1, false).unwrap();
+        writer.push(VALUE).unwrap();
+        assert!(writer.push(1).is_err());
+    }
+
+    #[test]
+    fn text_round_trip_works() {
+        let text = "6GGODyP2LIdbxIfYxy5UbN\n\n0000000000000000000001\n";
+        let mut bytes = Vec::new();
+        assert_eq!(from_text(text.as_bytes(), &mut bytes, true).unwrap(), 2);
+        assert_eq!(bytes, LIST);
+
+        let mut output = Vec::new();
+        to_text(&IdList::parse(&bytes).unwrap(), &mut output).unwrap();
+        assert_eq!(output, b"0000000000000000000001\n6GGODyP2LIdbxIfYxy5UbN\n");
+
+        let mut unsorted = Vec::new();
+        from_text(text.as_bytes(), &mut unsorted, false).unwrap();
+        let list = IdList::parse(&unsorted).unwrap();
+        assert!(!list.is_sorted());
+        assert_eq!(list.iter().collect::<Vec<_>>(), [VALUE, 1]);
+
+        let err = from_text(&b"6GGODyP2LIdbxIfYxy5UbN\nnope\n"[..], Vec::new(), false).unwrap_err();
+        assert_eq!(err.to_string(), "line 2 is not a valid id");
+    }
+
+    #[test]
+    fn mapped_id_list_works() {
+        let path = std::env::temp_dir().join(format!("rb62-id-list-{}.bin", std::process::id()));
+        std::fs::write(&path, LIST).unwrap();
+        let mapped = MappedIdList::open(&path).unwrap();
+        assert_eq!(mapped.list(), IdList::parse(&LIST).unwrap());
+        drop(mapped);
+
+        std::fs::write(&path, &LIST[..40]).unwrap();
+        assert_eq!(MappedIdList::open(&path).err().unwrap().kind(), ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
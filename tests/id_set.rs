@@ -0,0 +1,85 @@
+#![cfg(feature = "alloc")]
+use std::ops::Bound;
+
+use rb62::id_set::IdSet;
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+fn set(ids: &[u128]) -> IdSet {
+    ids.iter().copied().collect()
+}
+
+#[test]
+fn id_set_from_b62_and_hex_works() {
+    let from_b62 = IdSet::from_b62(&["6GGODyP2LIdbxIfYxy5UbN", "0000000000000000000001", "6GGODyP2LIdbxIfYxy5UbN"]).unwrap();
+    let from_hex = IdSet::from_hex(vec!["dbc3d5ebe344484da3e2448712a02213", "1"]).unwrap();
+    assert_eq!(from_b62, from_hex);
+    assert_eq!(from_b62.as_slice(), [1, VALUE]);
+
+    assert_eq!(IdSet::from_b62(&["6GGODyP2LIdbxIfYxy5UbN", "nope"]), None);
+    assert_eq!(IdSet::from_hex(&["xyz"]), None);
+}
+
+#[test]
+fn id_set_insert_remove_works() {
+    let mut ids = IdSet::new();
+    assert!(ids.is_empty());
+    assert!(ids.insert(VALUE));
+    assert!(ids.insert(1));
+    assert!(!ids.insert(VALUE));
+    assert!(ids.contains(VALUE));
+    assert!(!ids.contains(2));
+    assert!(ids.remove(1));
+    assert!(!ids.remove(1));
+    assert_eq!(ids.iter().collect::<Vec<_>>(), [VALUE]);
+    ids.extend(vec![3, 2, VALUE]);
+    assert_eq!(Vec::from(ids), [2, 3, VALUE]);
+}
+
+#[test]
+fn id_set_operations_work() {
+    let a = set(&[1, 2, 3, VALUE]);
+    let b = set(&[2, 4, VALUE, u128::MAX]);
+    assert_eq!(a.union(&b), set(&[1, 2, 3, 4, VALUE, u128::MAX]));
+    assert_eq!(a.intersection(&b), set(&[2, VALUE]));
+    assert_eq!(b.intersection(&a), set(&[2, VALUE]));
+    assert_eq!(a.difference(&b), set(&[1, 3]));
+    assert_eq!(b.difference(&a), set(&[4, u128::MAX]));
+    assert_eq!(a.union(&IdSet::new()), a);
+    assert!(a.intersection(&IdSet::new()).is_empty());
+}
+
+#[test]
+fn id_set_range_works() {
+    let ids = set(&[1, 2, 3, VALUE, u128::MAX]);
+    assert_eq!(ids.range(2..4).collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(ids.range(2..=VALUE).collect::<Vec<_>>(), [2, 3, VALUE]);
+    assert_eq!(ids.range(4..).collect::<Vec<_>>(), [VALUE, u128::MAX]);
+    assert_eq!(ids.range(..).count(), 5);
+    assert_eq!(ids.range((Bound::Excluded(3), Bound::Excluded(3))).count(), 0);
+}
+
+#[test]
+fn id_set_with_prefix_works() {
+    let ids = IdSet::from_b62(&[
+        "6GGODyP2LIdbxIfYxy5UbN",
+        "6GGODyP2LIdbxIfYxy5UbM",
+        "6GHODyP2LIdbxIfYxy5UbN",
+        "0000000000000000000001",
+        "7N42dgm5tFLK9N8MT7fHC7", // u128::MAX
+    ])
+    .unwrap();
+    let b62 = |prefix| ids.b62_with_prefix(prefix).unwrap().map(|b| String::from_utf8(b.to_vec()).unwrap()).collect::<Vec<_>>();
+    assert_eq!(b62("6GG"), ["6GGODyP2LIdbxIfYxy5UbM", "6GGODyP2LIdbxIfYxy5UbN"]);
+    assert_eq!(b62("6G"), ["6GGODyP2LIdbxIfYxy5UbM", "6GGODyP2LIdbxIfYxy5UbN", "6GHODyP2LIdbxIfYxy5UbN"]);
+    assert_eq!(b62("6GGODyP2LIdbxIfYxy5UbN"), ["6GGODyP2LIdbxIfYxy5UbN"]);
+    assert_eq!(b62("000"), ["0000000000000000000001"]);
+    assert_eq!(b62("7N"), ["7N42dgm5tFLK9N8MT7fHC7"]);
+    assert_eq!(b62("").len(), 5);
+    assert!(b62("6GGX").is_empty());
+    assert!(b62("7O").is_empty()); // above u128::MAX
+    assert!(b62("ZZZZZZZZZZZZZZZZZZZZZZ").is_empty());
+
+    assert!(ids.with_prefix("6G-").is_none());
+    assert!(ids.with_prefix("6GGODyP2LIdbxIfYxy5UbNN").is_none());
+}
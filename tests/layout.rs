@@ -0,0 +1,89 @@
+use rb62::layout::Layout;
+
+const LAYOUT: Layout = Layout::new().field("millis", 48).field("shard", 10).field("type", 6).field("random", 64);
+const PARTIAL: Layout = Layout::new().field("shard", 8).field("seq", 32);
+
+#[test]
+fn layout_fields_work() {
+    assert_eq!(LAYOUT.bits(), 128);
+    let names: Vec<_> = LAYOUT.fields().iter().map(|f| (f.name(), f.shift(), f.width())).collect();
+    assert_eq!(names, [("millis", 80, 48), ("shard", 70, 10), ("type", 64, 6), ("random", 0, 64)]);
+    assert_eq!(LAYOUT.field_by_name("shard").unwrap().max(), 1023);
+    assert_eq!(LAYOUT.field_by_name("random").unwrap().max(), u64::MAX as u128);
+    assert_eq!(Layout::new().field("all", 128).fields()[0].max(), u128::MAX);
+    assert!(LAYOUT.field_by_name("nope").is_none());
+}
+
+#[test]
+fn layout_pack_unpack_works() {
+    let id = LAYOUT.pack(&[1_600_000_000_000, 1023, 3, u64::MAX as u128]).unwrap();
+    assert_eq!(id, 1_600_000_000_000 << 80 | 1023 << 70 | 3 << 64 | u64::MAX as u128);
+    let fields = LAYOUT.unpack(id).unwrap();
+    assert_eq!(fields.id(), id);
+    assert_eq!(fields.get("millis"), Some(1_600_000_000_000));
+    assert_eq!(fields.get("shard"), Some(1023));
+    assert_eq!(fields.get("nope"), None);
+    assert_eq!(
+        fields.iter().collect::<Vec<_>>(),
+        [("millis", 1_600_000_000_000), ("shard", 1023), ("type", 3), ("random", u64::MAX as u128)]
+    );
+
+    let id = PARTIAL.pack(&[7, 42]).unwrap();
+    assert_eq!(id, 7 << 120 | 42 << 88);
+    assert_eq!(PARTIAL.unpack(id).unwrap().to_string(), "shard=7 seq=42");
+    assert_eq!(PARTIAL.unpack(id | 1), None);
+}
+
+#[test]
+fn layout_pack_rejects_invalid_input() {
+    let invalid: [&[u128]; 4] = [
+        &[1, 2, 3],                     // too few values
+        &[1, 2, 3, 4, 5],               // too many values
+        &[1, 1024, 3, 4],               // shard does not fit 10 bits
+        &[1 << 48, 2, 3, 4],            // millis does not fit 48 bits
+    ];
+    for values in invalid.iter() {
+        assert_eq!(LAYOUT.pack(values), None, "{:?}", values);
+        assert_eq!(LAYOUT.get_b62(values), None, "{:?}", values);
+    }
+}
+
+#[test]
+fn layout_b62_round_trip_works() {
+    let b62 = PARTIAL.get_b62(&[255, 1]).unwrap();
+    let b62 = std::str::from_utf8(&b62).unwrap();
+    assert_eq!(rb62::get_integer(b62), Some(255 << 120 | 1 << 88));
+    assert_eq!(PARTIAL.get_fields(b62).unwrap().to_string(), "shard=255 seq=1");
+    assert_eq!(PARTIAL.get_fields("6GGODyP2LIdbxIfYxy5UbN"), None); // low bits set
+    assert_eq!(PARTIAL.get_fields("not b62"), None);
+}
+
+#[test]
+#[should_panic]
+fn layout_rejects_more_than_128_bits() {
+    LAYOUT.field("extra", 1);
+}
+
+#[test]
+#[should_panic]
+fn layout_rejects_zero_width() {
+    Layout::new().field("empty", 0);
+}
+
+#[test]
+#[should_panic]
+fn layout_rejects_duplicate_name() {
+    PARTIAL.field("seq", 8);
+}
+
+#[test]
+#[should_panic]
+fn layout_rejects_empty_name() {
+    Layout::new().field("", 8);
+}
+
+#[test]
+fn layout_readme_example_works() {
+    let fields = LAYOUT.get_fields("02KfgytrvSZnBYZXL6TaNC").unwrap();
+    assert_eq!(fields.to_string(), "millis=1600000000000 shard=17 type=3 random=4660");
+}
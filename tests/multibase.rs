@@ -0,0 +1,106 @@
+use rb62::multibase::{Encoding, Registry, MAX_ID_LEN, STANDARD};
+
+const REGISTRY: Registry = STANDARD.with(b'6', Encoding::Base62);
+
+struct MultibaseTestData(&'static [u8], &'static [(Encoding, &'static str)]);
+
+// Test vectors from the multibase spec
+const SPEC_DATA: &[MultibaseTestData] = &[
+    MultibaseTestData(b"yes mani !", &[
+        (Encoding::Base16, "f796573206d616e692021"),
+        (Encoding::Base16Upper, "F796573206D616E692021"),
+        (Encoding::Base32, "bpfsxgidnmfxgsibb"),
+        (Encoding::Base32Upper, "BPFSXGIDNMFXGSIBB"),
+        (Encoding::Base36, "k2lcpzo5yikidynfl"),
+        (Encoding::Base36Upper, "K2LCPZO5YIKIDYNFL"),
+        (Encoding::Base58Btc, "z7paNL19xttacUY"),
+        (Encoding::Base64Url, "ueWVzIG1hbmkgIQ"),
+    ]),
+    MultibaseTestData(b"\x00yes mani !", &[
+        (Encoding::Base16, "f00796573206d616e692021"),
+        (Encoding::Base32, "bab4wk4zanvqw42jaee"),
+        (Encoding::Base36, "k02lcpzo5yikidynfl"),
+        (Encoding::Base58Btc, "z17paNL19xttacUY"),
+        (Encoding::Base64Url, "uAHllcyBtYW5pICE"),
+    ]),
+    MultibaseTestData(b"\x00\x00yes mani !", &[
+        (Encoding::Base16, "f0000796573206d616e692021"),
+        (Encoding::Base32, "baaahszltebwwc3tjeaqq"),
+        (Encoding::Base36, "k002lcpzo5yikidynfl"),
+        (Encoding::Base58Btc, "z117paNL19xttacUY"),
+        (Encoding::Base64Url, "uAAB5ZXMgbWFuaSAh"),
+    ]),
+];
+
+// (id, encoding, expected)
+const ID_DATA: &[(u128, Encoding, &str)] = &[
+    (0xdbc3d5ebe344484da3e2448712a02213, Encoding::Base16, "fdbc3d5ebe344484da3e2448712a02213"),
+    (0xdbc3d5ebe344484da3e2448712a02213, Encoding::Base32, "b3pb5l27diree3i7cisdrfibccm"),
+    (0xdbc3d5ebe344484da3e2448712a02213, Encoding::Base36, "kd0drfmh4zte1oh8bkj51y5onn"),
+    (0xdbc3d5ebe344484da3e2448712a02213, Encoding::Base58Btc, "zU8ycDbEa8WMR5kynpiJriE"),
+    (0xdbc3d5ebe344484da3e2448712a02213, Encoding::Base64Url, "u28PV6-NESE2j4kSHEqAiEw"),
+    (0xdbc3d5ebe344484da3e2448712a02213, Encoding::Base62, "66GGODyP2LIdbxIfYxy5UbN"),
+    (0x00c3d5ebe344484da3e2448712a02213, Encoding::Base36, "k01mozv14l8567vbczavfhbsib"),
+    (0x00c3d5ebe344484da3e2448712a02213, Encoding::Base58Btc, "z16UmwAZb6h3Ru6mMACbroc"),
+    (0, Encoding::Base58Btc, "z1111111111111111"),
+    (0, Encoding::Base62, "60000000000000000000000"),
+];
+
+#[test]
+fn multibase_spec_vectors_work() {
+    for MultibaseTestData(bytes, encoded) in SPEC_DATA {
+        for (encoding, expected) in encoded.iter() {
+            let mut out = [0u8; 64];
+            assert_eq!(STANDARD.encode(*encoding, bytes, &mut out), Some(*expected));
+            let mut out = [0u8; 64];
+            assert_eq!(STANDARD.decode(expected, &mut out), Some((*encoding, *bytes)), "decoding {}", expected);
+        }
+    }
+}
+
+#[test]
+fn multibase_ids_work() {
+    for (id, encoding, expected) in ID_DATA {
+        let mut buf = [0u8; MAX_ID_LEN];
+        assert_eq!(REGISTRY.encode_id(*encoding, *id, &mut buf), Some(*expected));
+        assert_eq!(REGISTRY.decode_id(expected), Some((*encoding, *id)));
+    }
+}
+
+#[test]
+fn multibase_decoding_ignores_case_where_the_spec_does() {
+    let mut out = [0u8; 64];
+    assert_eq!(STANDARD.decode("fDBC3", &mut out), Some((Encoding::Base16, &[0xdb, 0xc3][..])));
+    assert_eq!(STANDARD.decode_id("KD0DRFMH4ZTE1OH8BKJ51Y5ONN"), STANDARD.decode_id("kd0drfmh4zte1oh8bkj51y5onn")
+        .map(|(_, id)| (Encoding::Base36Upper, id)));
+}
+
+#[test]
+fn multibase_should_return_none_when_input_invalid() {
+    let invalid_inputs = [
+        "",                                      // No code
+        "6GGODyP2LIdbxIfYxy5UbN",                // b62 without its code
+        "x6GGODyP2LIdbxIfYxy5UbN",               // Unknown code
+        "fdbc3d5ebe344484da3e2448712a0221",      // Only 15.5 bytes
+        "fdbc3d5ebe344484da3e2448712a022133",    // 17 bytes
+        "zU8ycDbEa8WMR5kynpiJri0",               // 0 is not base58btc
+        "u28PV6-NESE2j4kSHEqAiEx",               // Unused bits are set
+        "u28PV6-NESE2j4kSHEqAiEw==",             // Padding
+        "67N42dgm5tFLK9N8MT7fHC8",               // b62 too large
+    ];
+    for invalid in &invalid_inputs {
+        assert_eq!(REGISTRY.decode_id(invalid), None, "{} should not parse", invalid);
+    }
+    // b62 is not registered in the standard registry
+    let mut buf = [0u8; MAX_ID_LEN];
+    assert_eq!(STANDARD.encode_id(Encoding::Base62, 1, &mut buf), None);
+    // output buffer too small
+    let mut out = [0u8; 8];
+    assert_eq!(STANDARD.encode(Encoding::Base58Btc, b"yes mani !", &mut out), None);
+}
+
+#[test]
+#[should_panic]
+fn multibase_registry_rejects_taken_code() {
+    STANDARD.with(b'z', Encoding::Base62);
+}
@@ -0,0 +1,57 @@
+use rb62::prefixed::{PrefixRegistry, PrefixedIdError, MAX_PREFIXED_ID_LEN};
+
+const IDS: PrefixRegistry = PrefixRegistry::new(b'_').with("trk").with("alb").with("art");
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+#[test]
+fn prefixed_id_parse_works() {
+    let id = IDS.parse("trk_6GGODyP2LIdbxIfYxy5UbN").expect("prefixed id can be parsed");
+    assert_eq!(id.prefix(), "trk");
+    assert_eq!(id.value(), VALUE);
+    assert_eq!(id.to_string(), "trk_6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(IDS.parse_expecting("trk", "trk_6GGODyP2LIdbxIfYxy5UbN"), Ok(VALUE));
+}
+
+#[test]
+fn prefixed_id_format_works() {
+    let mut buf = [0u8; MAX_PREFIXED_ID_LEN];
+    assert_eq!(IDS.format("alb", VALUE, &mut buf), Ok("alb_6GGODyP2LIdbxIfYxy5UbN"));
+    assert_eq!(IDS.format("xyz", VALUE, &mut buf), Err(PrefixedIdError::UnknownPrefix));
+
+    let dashed = PrefixRegistry::new(b'-').with("trk");
+    assert_eq!(dashed.format("trk", 0, &mut buf), Ok("trk-0000000000000000000000"));
+    assert_eq!(dashed.parse("trk_6GGODyP2LIdbxIfYxy5UbN"), Err(PrefixedIdError::MissingSeparator));
+}
+
+#[test]
+fn prefixed_id_parse_should_return_specific_errors() {
+    let invalid_inputs = [
+        ("6GGODyP2LIdbxIfYxy5UbN", PrefixedIdError::MissingSeparator),
+        ("xyz_6GGODyP2LIdbxIfYxy5UbN", PrefixedIdError::UnknownPrefix),
+        ("TRK_6GGODyP2LIdbxIfYxy5UbN", PrefixedIdError::UnknownPrefix),   // Prefixes are case sensitive
+        ("_6GGODyP2LIdbxIfYxy5UbN", PrefixedIdError::UnknownPrefix),
+        ("trk_6GGODyP2LIdbxIfYxy5Ub", PrefixedIdError::InvalidBody),     // Too short
+        ("trk_7N42dgm5tFLK9N8MT7fHC8", PrefixedIdError::InvalidBody),    // Too large
+        ("trk__6GGODyP2LIdbxIfYxy5UbN", PrefixedIdError::InvalidBody),
+    ];
+    for (invalid, error) in &invalid_inputs {
+        assert_eq!(IDS.parse(invalid), Err(*error), "{} should not parse", invalid);
+    }
+
+    let mismatch = IDS.parse_expecting("alb", "trk_6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(mismatch, Err(PrefixedIdError::PrefixMismatch { expected: "alb", found: "trk" }));
+    assert_eq!(mismatch.unwrap_err().to_string(), "expected an id with prefix alb, found prefix trk");
+    assert_eq!(IDS.parse_expecting("xyz", "trk_6GGODyP2LIdbxIfYxy5UbN"), Err(PrefixedIdError::UnknownPrefix));
+}
+
+#[test]
+#[should_panic]
+fn prefix_registry_rejects_duplicate_prefix() {
+    IDS.with("trk");
+}
+
+#[test]
+#[should_panic]
+fn prefix_registry_rejects_b62_separator() {
+    PrefixRegistry::new(b'x');
+}
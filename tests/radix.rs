@@ -0,0 +1,97 @@
+use rb62::radix::{width, Radix, BASE36, BASE58, BASE62, CROCKFORD32};
+use std::str;
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+#[test]
+fn radix_width_works() {
+    assert_eq!(width(2), 128);
+    assert_eq!(width(16), 32);
+    assert_eq!(width(32), 26);
+    assert_eq!(width(36), 25);
+    assert_eq!(width(58), 22);
+    assert_eq!(width(62), 22);
+}
+
+#[test]
+fn radix_instances_work() {
+    assert_eq!(str::from_utf8(&BASE62.encode(VALUE)).unwrap(), "6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(str::from_utf8(&BASE36.encode(VALUE)).unwrap(), "d0drfmh4zte1oh8bkj51y5onn");
+    assert_eq!(str::from_utf8(&BASE58.encode(VALUE)).unwrap(), "U8ycDbEa8WMR5kynpiJriE");
+    assert_eq!(str::from_utf8(&CROCKFORD32.encode(VALUE)).unwrap(), "6VRFAYQRT4916T7RJ4GW9A08GK");
+
+    assert_eq!(BASE62.decode("6GGODyP2LIdbxIfYxy5UbN"), Some(VALUE));
+    assert_eq!(BASE36.decode("d0drfmh4zte1oh8bkj51y5onn"), Some(VALUE));
+    assert_eq!(BASE58.decode("U8ycDbEa8WMR5kynpiJriE"), Some(VALUE));
+    assert_eq!(CROCKFORD32.decode("6VRFAYQRT4916T7RJ4GW9A08GK"), Some(VALUE));
+}
+
+#[test]
+fn radix_unpadded_works() {
+    let mut buf = [0u8; 22];
+    assert_eq!(BASE58.encode_unpadded(0, &mut buf), "1");
+    assert_eq!(BASE58.encode_unpadded(58, &mut buf), "21");
+    assert_eq!(BASE58.decode_unpadded("21"), Some(58));
+    assert_eq!(BASE58.decode_unpadded("U8ycDbEa8WMR5kynpiJriE"), Some(VALUE));
+    let mut buf = [0u8; 26];
+    assert_eq!(CROCKFORD32.encode_unpadded(1024, &mut buf), "100");
+    assert_eq!(CROCKFORD32.decode_unpadded("1oo"), Some(1024));
+    assert_eq!(CROCKFORD32.decode_unpadded(""), None);
+    assert_eq!(CROCKFORD32.decode_unpadded("U"), None);
+}
+
+#[test]
+fn radix_max_values_work() {
+    assert_eq!(BASE62.decode("7N42dgm5tFLK9N8MT7fHC7"), Some(u128::MAX));
+    assert_eq!(BASE36.decode("f5lxx1zz5pnorynqglhzmsp33"), Some(u128::MAX));
+    assert_eq!(BASE58.decode("YcVfxkQb6JRzqk5kF2tNLv"), Some(u128::MAX));
+    assert_eq!(CROCKFORD32.decode("7ZZZZZZZZZZZZZZZZZZZZZZZZZ"), Some(u128::MAX));
+
+    assert_eq!(BASE62.decode("7N42dgm5tFLK9N8MT7fHC8"), None);
+    assert_eq!(BASE36.decode("f5lxx1zz5pnorynqglhzmsp34"), None);
+    assert_eq!(BASE58.decode("YcVfxkQb6JRzqk5kF2tNLw"), None);
+    assert_eq!(CROCKFORD32.decode("80000000000000000000000000"), None);
+    assert_eq!(BASE58.decode("1111111111111111111111"), Some(0));
+}
+
+#[test]
+fn radix_case_and_alias_rules_work() {
+    assert_eq!(BASE36.decode("D0DRFMH4ZTE1OH8BKJ51Y5ONN"), Some(VALUE));
+    assert_eq!(CROCKFORD32.decode("6vrfayqrt4916t7rj4gw9a08gk"), Some(VALUE));
+    // I, L and O are read as 1, 1 and 0
+    assert_eq!(CROCKFORD32.decode("6VRFAYQRT49I6T7RJ4GW9AO8GK"), Some(VALUE));
+    assert_eq!(CROCKFORD32.decode("6VRFAYQRT49l6T7RJ4GW9Ao8GK"), Some(VALUE));
+    // U is not part of Crockford base32
+    assert_eq!(CROCKFORD32.decode("6VRFAYQRT4916T7RJ4GW9A08GU"), None);
+    // base58 has no 0, O, I or l
+    for invalid in &["0111111111111111111111", "O111111111111111111111", "I111111111111111111111", "l111111111111111111111"] {
+        assert_eq!(BASE58.decode(invalid), None, "{} should not parse", invalid);
+    }
+    // base62 is case sensitive
+    assert_ne!(BASE62.decode("6ggodyp2lidbxifyxy5ubn"), Some(VALUE));
+}
+
+#[test]
+fn radix_custom_instance_works() {
+    const BINARY: Radix<128> = Radix::new(b"01");
+    assert_eq!(BINARY.decode(&format!("{:0128b}", VALUE)), Some(VALUE));
+    assert_eq!(str::from_utf8(&BINARY.encode(VALUE)).unwrap(), format!("{:0128b}", VALUE));
+}
+
+#[test]
+#[should_panic]
+fn radix_rejects_wrong_width() {
+    Radix::<21>::new(b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
+}
+
+#[test]
+#[should_panic]
+fn radix_rejects_repeated_chars() {
+    Radix::<32>::new(b"0123456789abcdea");
+}
+
+#[test]
+#[should_panic]
+fn radix_rejects_ambiguous_case_insensitivity() {
+    BASE62.case_insensitive();
+}
@@ -0,0 +1,79 @@
+use rb62::scan::{rewrite, scan, Format, Hit, HitKind};
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+fn hits(text: &str) -> Vec<(HitKind, &str, Option<u128>)> {
+    scan(text.as_bytes()).map(|hit| (hit.kind, &text[hit.span], hit.id)).collect()
+}
+
+#[test]
+fn scan_finds_ids() {
+    let text = "id=6GGODyP2LIdbxIfYxy5UbN, gid: dbc3d5ebe344484da3e2448712a02213.\nDBC3D5EBE344484DA3E2448712A02213";
+    assert_eq!(hits(text), vec![
+        (HitKind::Base62, "6GGODyP2LIdbxIfYxy5UbN", Some(VALUE)),
+        (HitKind::Hex, "dbc3d5ebe344484da3e2448712a02213", Some(VALUE)),
+        (HitKind::Hex, "DBC3D5EBE344484DA3E2448712A02213", Some(VALUE)),
+    ]);
+}
+
+#[test]
+fn scan_finds_uris() {
+    let text = "a spotify:track:6GGODyP2LIdbxIfYxy5UbN. b (spotify:user:j%C3%B6rg:playlist:6GGODyP2LIdbxIfYxy5UbN) \
+                c spotify:user:bob:collection, d spotify:local:Daft+Punk::One+More+Time:320.";
+    assert_eq!(hits(text), vec![
+        (HitKind::Uri, "spotify:track:6GGODyP2LIdbxIfYxy5UbN", Some(VALUE)),
+        (HitKind::Uri, "spotify:user:j%C3%B6rg:playlist:6GGODyP2LIdbxIfYxy5UbN", Some(VALUE)),
+        (HitKind::Uri, "spotify:user:bob:collection", None),
+        (HitKind::Uri, "spotify:local:Daft+Punk::One+More+Time:320", None),
+    ]);
+
+    let hit = scan(b"spotify:album:6GGODyP2LIdbxIfYxy5UbN").next().unwrap();
+    assert_eq!(hit, Hit { kind: HitKind::Uri, span: 0..36, id_span: Some(14..36), id: Some(VALUE) });
+}
+
+#[test]
+fn scan_skips_non_ids() {
+    let texts = [
+        "internationalizational",                     // 22 letters, too large for 128 bits
+        "x6GGODyP2LIdbxIfYxy5UbN",                    // Part of a longer word
+        "6GGODyP2LIdbxIfYxy5UbNx",
+        "dbc3d5ebe344484da3e2448712a022134",          // 33 hex digits
+        "dbc3d5ebe344484da3e2448712a0221",            // 31 hex digits
+        "spotify:song:6GGODyP2LIdbxIfYxy5UbN1",       // Unknown kind, and the id is part of a longer word
+        "spotify:track:6GGODyP2LIdbxIfYxy5Ub",        // Short id
+        "spotify:local:a:b:c:",                       // No duration
+    ];
+    for text in &texts {
+        assert_eq!(hits(text), vec![], "{} should have no hits", text);
+    }
+    // an invalid URI can still contain a valid id
+    assert_eq!(hits("spotify:track:6GGODyP2LIdbxIfYxy5UbN:extra"), vec![
+        (HitKind::Base62, "6GGODyP2LIdbxIfYxy5UbN", Some(VALUE)),
+    ]);
+}
+
+#[test]
+fn rewrite_works() {
+    let text = b"gid dbc3d5ebe344484da3e2448712a02213 is spotify:track:6GGODyP2LIdbxIfYxy5UbN, \xff not utf8";
+    let mut out = Vec::new();
+    rewrite(text, |hit| match hit.kind {
+        HitKind::Hex => Some(Format::Base62),
+        HitKind::Uri => Some(Format::UuidHex),
+        HitKind::Base62 => None,
+    }, |bytes| out.extend_from_slice(bytes));
+    assert_eq!(
+        out,
+        &b"gid 6GGODyP2LIdbxIfYxy5UbN is spotify:track:dbc3d5eb-e344-484d-a3e2-448712a02213, \xff not utf8"[..]
+    );
+
+    let mut out = Vec::new();
+    rewrite(b"no ids here", |_| Some(Format::Hex), |bytes| out.extend_from_slice(bytes));
+    assert_eq!(out, b"no ids here");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn rewrite_to_vec_works() {
+    let out = rb62::scan::rewrite_to_vec(b"6GGODyP2LIdbxIfYxy5UbN", |_| Some(Format::Hex));
+    assert_eq!(out, b"dbc3d5ebe344484da3e2448712a02213");
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "std")]
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+
+use rb62::sequence::{get_code, FileStorage, MemoryStorage, SequenceAllocator, SequenceError, Storage};
+
+#[test]
+fn sequence_allocator_works() {
+    let allocator = SequenceAllocator::new(MemoryStorage::new(), 3).unwrap();
+    assert_eq!(allocator.high_water_mark(), 0);
+    let values: Vec<u128> = (0..4).map(|_| allocator.next().unwrap()).collect();
+    assert_eq!(values, [0, 1, 2, 3]);
+    assert_eq!(allocator.high_water_mark(), 6);
+
+    // a restart skips the rest of the block
+    let allocator = SequenceAllocator::new(allocator.into_storage(), 3).unwrap();
+    assert_eq!(allocator.next(), Ok(6));
+    assert_eq!(allocator.high_water_mark(), 9);
+}
+
+#[test]
+fn sequence_allocator_codes_work() {
+    let allocator = SequenceAllocator::new(MemoryStorage::with_value(61), 10).unwrap();
+    assert_eq!(allocator.next_code().unwrap(), "Z");
+    assert_eq!(allocator.next_code().unwrap(), "10");
+}
+
+#[test]
+fn sequence_allocator_is_exhausted_at_max() {
+    let allocator = SequenceAllocator::new(MemoryStorage::with_value(u128::MAX - 2), 10).unwrap();
+    assert_eq!(allocator.next(), Ok(u128::MAX - 2));
+    assert_eq!(allocator.next(), Ok(u128::MAX - 1));
+    assert_eq!(allocator.next(), Err(SequenceError::Exhausted));
+}
+
+#[test]
+fn sequence_allocator_is_thread_safe() {
+    let allocator = Arc::new(SequenceAllocator::new(MemoryStorage::new(), 7).unwrap());
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let allocator = Arc::clone(&allocator);
+            thread::spawn(move || (0..1000).map(|_| allocator.next().unwrap()).collect::<Vec<_>>())
+        })
+        .collect();
+    let mut all = HashSet::new();
+    for thread in threads {
+        let values = thread.join().unwrap();
+        assert!(values.windows(2).all(|w| w[0] < w[1]));
+        all.extend(values);
+    }
+    assert_eq!(all.len(), 8000);
+    assert_eq!(all.iter().max(), Some(&7999));
+}
+
+#[test]
+fn file_storage_works() {
+    let path = std::env::temp_dir().join(format!("rb62-sequence-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut storage = FileStorage::new(&path);
+    assert_eq!(storage.path(), path);
+    assert_eq!(storage.load().unwrap(), None);
+
+    let allocator = SequenceAllocator::new(storage, 100).unwrap();
+    assert_eq!(allocator.next().unwrap(), 0);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "100\n");
+
+    let allocator = SequenceAllocator::new(FileStorage::new(&path), 100).unwrap();
+    assert_eq!(allocator.next().unwrap(), 100);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "200\n");
+
+    std::fs::write(&path, "garbage").unwrap();
+    assert_eq!(FileStorage::new(&path).load().unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn get_code_works() {
+    let mut buf = [0u8; 22];
+    assert_eq!(get_code(0, &mut buf), "0");
+    assert_eq!(get_code(61, &mut buf), "Z");
+    assert_eq!(get_code(62, &mut buf), "10");
+    assert_eq!(get_code(0xdbc3d5ebe344484da3e2448712a02213, &mut buf), "6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(get_code(u128::MAX, &mut buf), "7N42dgm5tFLK9N8MT7fHC7");
+}
@@ -0,0 +1,73 @@
+use rb62::get_integer;
+use rb62::shard::{fold, jump_hash, node_hash, rendezvous, rendezvous_score};
+
+const VECTORS: &str = include_str!("data/shard_vectors.csv");
+const NODES: [&str; 5] = ["db-1", "db-2", "db-3", "db-4", "db-5"];
+
+#[test]
+fn shard_matches_test_vectors() {
+    let mut lines = VECTORS.lines().filter(|line| !line.starts_with('#'));
+    assert_eq!(lines.next(), Some("id_b62,fold_hex,jump_1,jump_2,jump_10,jump_1000,jump_65536,rendezvous_5,rendezvous_3"));
+    let mut count = 0;
+    for line in lines {
+        let fields: Vec<&str> = line.split(',').collect();
+        let id = get_integer(fields[0]).unwrap();
+        assert_eq!(format!("{:016x}", fold(id)), fields[1], "{}", line);
+        for (buckets, expected) in [1, 2, 10, 1000, 65536].iter().zip(&fields[2..7]) {
+            assert_eq!(jump_hash(id, *buckets).unwrap().to_string(), *expected, "{}", line);
+        }
+        assert_eq!(rendezvous(id, &NODES).unwrap().to_string(), fields[7], "{}", line);
+        assert_eq!(rendezvous(id, &NODES[..3]).unwrap().to_string(), fields[8], "{}", line);
+        count += 1;
+    }
+    assert_eq!(count, 16);
+}
+
+#[test]
+fn node_hash_matches_test_vectors() {
+    for line in VECTORS.lines().filter(|line| line.starts_with("# db-")) {
+        let mut fields = line[2..].split(' ');
+        let (name, hash) = (fields.next().unwrap(), fields.next().unwrap());
+        assert_eq!(format!("{:016x}", node_hash(name)), hash);
+    }
+    // the FNV-1a test vectors
+    assert_eq!(node_hash(""), 0xcbf29ce484222325);
+    assert_eq!(node_hash("a"), 0xaf63dc4c8601ec8c);
+}
+
+#[test]
+fn jump_hash_is_consistent() {
+    assert_eq!(jump_hash(1, 0), None);
+    for i in 0..1000u128 {
+        let id = i.wrapping_mul(0x9e3779b97f4a7c15f39cc0605cedc834);
+        let mut previous = jump_hash(id, 1).unwrap();
+        assert_eq!(previous, 0);
+        for buckets in 2..50 {
+            let bucket = jump_hash(id, buckets).unwrap();
+            assert!(bucket == previous || bucket == buckets - 1);
+            previous = bucket;
+        }
+    }
+}
+
+#[test]
+fn rendezvous_only_moves_ids_of_removed_node() {
+    let empty: [&str; 0] = [];
+    assert_eq!(rendezvous(1, &empty), None);
+    let mut counts = [0; 5];
+    for i in 0..5000u128 {
+        let id = i.wrapping_mul(0x9e3779b97f4a7c15f39cc0605cedc834);
+        let node = rendezvous(id, &NODES).unwrap();
+        counts[node] += 1;
+        let without_db_3 = ["db-1", "db-2", "db-4", "db-5"];
+        let moved = rendezvous(id, &without_db_3).unwrap();
+        if node != 2 {
+            assert_eq!(without_db_3[moved], NODES[node]);
+        }
+        assert_eq!(
+            rendezvous_score(id, node_hash(NODES[node])),
+            NODES.iter().map(|n| rendezvous_score(id, node_hash(n))).max().unwrap()
+        );
+    }
+    assert!(counts.iter().all(|c| (800..1200).contains(c)), "{:?}", counts);
+}
@@ -0,0 +1,210 @@
+#![cfg(feature = "std")]
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use rb62::sequence::{MemoryStorage, SequenceAllocator};
+use rb62::shortlink::{CodeGenerator, FileStore, HashCodes, LinkStore, MemoryStore, ShortlinkError, Shortener};
+
+const URL: &str = "https://open.spotify.com/track/6GGODyP2LIdbxIfYxy5UbN";
+
+fn sequence_shortener() -> Shortener<MemoryStore, SequenceAllocator<MemoryStorage>> {
+    Shortener::new(MemoryStore::new(), SequenceAllocator::new(MemoryStorage::with_value(61), 10).unwrap())
+}
+
+fn request(shortener: &Shortener<MemoryStore, SequenceAllocator<MemoryStorage>>, request: &str) -> String {
+    let mut output = Vec::new();
+    shortener.handle(request.as_bytes(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn shortener_with_sequence_works() {
+    let shortener = sequence_shortener();
+    assert_eq!(shortener.shorten(URL).unwrap(), "Z");
+    assert_eq!(shortener.shorten("https://spotify.com").unwrap(), "10");
+    assert_eq!(shortener.shorten(URL).unwrap(), "Z");
+    assert_eq!(shortener.resolve("Z").as_deref(), Some(URL));
+    assert_eq!(shortener.resolve("11"), None);
+}
+
+#[test]
+fn shortener_rejects_invalid_urls() {
+    let shortener = sequence_shortener();
+    let long = "x".repeat(rb62::shortlink::MAX_URL_LEN + 1);
+    let invalid = [
+        "",                         // empty
+        "https://spotify.com/a b",  // whitespace
+        "https://spotify.com/\t",   // control char
+        long.as_str(),              // too long
+        "javascript:alert(1)",      // not http
+        "//evil.example",           // protocol relative
+        "ftp://spotify.com",        // not http
+        "spotify.com",              // not absolute
+        "https://",                 // no host
+        "https:///track",           // no host
+        "https://user@:8080/",      // no host
+    ];
+    for url in invalid.iter() {
+        assert!(matches!(shortener.shorten(url), Err(ShortlinkError::InvalidUrl)), "{}", url);
+    }
+}
+
+#[test]
+fn hash_codes_resolve_collisions() {
+    let codes = HashCodes::new(6);
+    let first = codes.code(URL, 0).unwrap();
+    assert_eq!(first.len(), 6);
+    assert_eq!(codes.code(URL, 0).unwrap(), first);
+    assert_ne!(codes.code(URL, 1).unwrap(), first);
+
+    // with one char codes, a few dozen urls must collide and still get distinct codes
+    let shortener = Shortener::new(MemoryStore::new(), HashCodes::new(1));
+    let mut seen = std::collections::HashSet::new();
+    for i in 0..20 {
+        let url = format!("https://spotify.com/{}", i);
+        let code = shortener.shorten(&url).unwrap();
+        assert_eq!(shortener.resolve(&code), Some(url));
+        assert!(seen.insert(code));
+    }
+}
+
+#[test]
+fn shortener_gives_up_when_codes_are_exhausted() {
+    let mut store = MemoryStore::new();
+    let codes = HashCodes::new(1);
+    for attempt in 0..rb62::shortlink::MAX_ATTEMPTS {
+        store.insert(&codes.code(URL, attempt).unwrap(), "https://taken").unwrap();
+    }
+    let shortener = Shortener::new(store, codes);
+    assert!(matches!(shortener.shorten(URL), Err(ShortlinkError::Exhausted)));
+}
+
+#[test]
+#[should_panic]
+fn hash_codes_reject_long_codes() {
+    HashCodes::new(11);
+}
+
+#[test]
+fn file_store_works() {
+    let path = std::env::temp_dir().join(format!("rb62-shortlink-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut store = FileStore::open(&path).unwrap();
+    assert!(store.is_empty());
+    assert!(store.insert("abc", URL).unwrap());
+    assert!(!store.insert("abc", "https://spotify.com").unwrap());
+    assert!(store.insert("abd", "https://spotify.com").unwrap());
+    drop(store);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), format!("abc\t{}\nabd\thttps://spotify.com\n", URL));
+
+    // a torn last line is cut off
+    std::fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"abe\thttps://spo").unwrap();
+    let mut store = FileStore::open(&path).unwrap();
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.url("abc").as_deref(), Some(URL));
+    assert_eq!(store.code("https://spotify.com").as_deref(), Some("abd"));
+    assert!(store.insert("abe", "https://spotify.com/e").unwrap());
+
+    // links which would not read back are rejected before writing
+    assert_eq!(store.insert("a\tb", URL).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(store.insert("abf", "https://spotify.com/\n").unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(store.insert("abf", "javascript:alert(1)").unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    drop(store);
+    let store = FileStore::open(&path).unwrap();
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.url("abe").as_deref(), Some("https://spotify.com/e"));
+    drop(store);
+
+    std::fs::write(&path, "abc\tjavascript:alert(1)\n").unwrap();
+    assert_eq!(FileStore::open(&path).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::write(&path, "no tab\n").unwrap();
+    assert_eq!(FileStore::open(&path).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn handle_works() {
+    let shortener = sequence_shortener();
+    let created = request(&shortener, &format!("POST / HTTP/1.1\r\nHost: s\r\nContent-Length: {}\r\n\r\n{}", URL.len(), URL));
+    assert_eq!(
+        created,
+        "HTTP/1.1 201 Created\r\nLocation: /Z\r\nContent-Type: text/plain\r\nContent-Length: 2\r\nConnection: close\r\n\r\nZ\n"
+    );
+    let redirect = request(&shortener, "GET /Z?utm=x HTTP/1.1\r\nHost: s\r\n\r\n");
+    assert_eq!(
+        redirect,
+        format!("HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Type: text/plain\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", URL)
+    );
+}
+
+#[test]
+fn handle_rejects_invalid_requests() {
+    let shortener = sequence_shortener();
+    let invalid = [
+        ("GET /nope HTTP/1.1\r\n\r\n", "404 Not Found"),
+        ("GET / HTTP/1.1\r\n\r\n", "404 Not Found"),
+        ("GET é HTTP/1.1\r\n\r\n", "404 Not Found"),
+        ("POST /x HTTP/1.1\r\n\r\n", "404 Not Found"),
+        ("DELETE /Z HTTP/1.1\r\n\r\n", "405 Method Not Allowed"),
+        ("POST / HTTP/1.1\r\nContent-Length: 3\r\n\r\na b", "400 Bad Request"),
+        ("POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort", "400 Bad Request"),
+        ("POST / HTTP/1.1\r\nContent-Length: 99999\r\n\r\n", "400 Bad Request"),
+        ("GET /Z\r\n\r\n", "400 Bad Request"),
+        ("GET /Z HTTP/1.1\r\n", "400 Bad Request"),
+    ];
+    for (input, status) in invalid.iter() {
+        let response = request(&shortener, input);
+        assert!(response.starts_with(&format!("HTTP/1.1 {}\r\n", status)), "{:?}: {}", input, response);
+    }
+
+    for input in [&b"GET /\xff HTTP/1.1\r\n\r\n"[..], b"GET /Z HTTP/1.1\r\nHost: \xff\r\n\r\n"].iter() {
+        let mut output = Vec::new();
+        shortener.handle(*input, &mut output).unwrap();
+        assert!(output.starts_with(b"HTTP/1.1 400 Bad Request\r\n"), "{:?}", input);
+    }
+}
+
+#[test]
+fn serve_works() {
+    let shortener = Arc::new(sequence_shortener());
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Arc::clone(&shortener);
+    thread::spawn(move || server.serve(listener));
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", URL.len(), URL).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.ends_with("\r\n\r\nZ\n"), "{}", response);
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(b"GET /Z HTTP/1.1\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with(&format!("HTTP/1.1 302 Found\r\nLocation: {}\r\n", URL)), "{}", response);
+}
+
+#[test]
+fn serve_drops_slow_clients() {
+    let shortener = Arc::new(sequence_shortener().timeout(Duration::from_millis(100)));
+    shortener.shorten(URL).unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Arc::clone(&shortener);
+    thread::spawn(move || server.serve(listener));
+
+    // connects and never finishes its request
+    let mut slow = TcpStream::connect(addr).unwrap();
+    slow.write_all(b"GET /Z HTTP/1.1\r\n").unwrap();
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    stream.write_all(b"GET /Z HTTP/1.1\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 302 Found\r\n"), "{}", response);
+}
@@ -0,0 +1,103 @@
+use std::cell::Cell;
+
+use rb62::snowflake::{get_b62_from_u64, get_u64, Generator, Snowflake, SnowflakeConfig, SnowflakeError};
+
+const TWITTER_EPOCH: u64 = 1_288_834_974_657;
+const CONFIG: SnowflakeConfig = SnowflakeConfig::new(TWITTER_EPOCH);
+
+#[test]
+fn snowflake_config_works() {
+    assert_eq!(CONFIG.timestamp_bits(), 41);
+    assert_eq!(CONFIG.max_worker(), 1023);
+    assert_eq!(CONFIG.max_sequence(), 4095);
+    let narrow = SnowflakeConfig::new(0).worker_bits(5).sequence_bits(8);
+    assert_eq!((narrow.timestamp_bits(), narrow.max_worker(), narrow.max_sequence()), (50, 31, 255));
+}
+
+#[test]
+fn snowflake_decode_works() {
+    let snowflake = CONFIG.decode(1_212_092_628_029_698_048);
+    assert_eq!(snowflake, Snowflake { millis: 1_577_820_376_771, worker: 327, sequence: 0 });
+    let id = (1_577_820_376_771 - TWITTER_EPOCH) << 22 | 327 << 12 | 4095;
+    assert_eq!(CONFIG.decode(id), Snowflake { millis: 1_577_820_376_771, worker: 327, sequence: 4095 });
+    assert_eq!(CONFIG.decode_b62("1rkBO8smJ5i"), Some(CONFIG.decode(get_u64("1rkBO8smJ5i").unwrap())));
+    assert_eq!(CONFIG.decode_b62("nope"), None);
+}
+
+#[test]
+fn generator_works() {
+    let now = Cell::new(1_600_000_000_000u64);
+    let config = CONFIG.sequence_bits(2);
+    let mut generator = Generator::new(config, 5, || now.get()).unwrap();
+    let ids: Vec<u64> = (0..4).map(|_| generator.next_id().unwrap()).collect();
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(config.decode(*id), Snowflake { millis: 1_600_000_000_000, worker: 5, sequence: i as u64 });
+    }
+    assert_eq!(generator.next_id(), Err(SnowflakeError::SequenceExhausted));
+
+    now.set(now.get() + 1);
+    let id = generator.next_id().unwrap();
+    assert_eq!(config.decode(id), Snowflake { millis: 1_600_000_000_001, worker: 5, sequence: 0 });
+
+    now.set(now.get() - 2);
+    assert_eq!(
+        generator.next_id(),
+        Err(SnowflakeError::ClockMovedBackwards { last_millis: 1_600_000_000_001, now_millis: 1_599_999_999_999 })
+    );
+    assert_eq!(generator.next_id().unwrap_err().to_string(), "clock moved backwards from 1600000000001 to 1599999999999");
+}
+
+#[test]
+fn generator_starts_at_sequence_0_at_the_epoch() {
+    let config = SnowflakeConfig::new(0);
+    let mut generator = Generator::new(config, 0, || 0u64).unwrap();
+    assert_eq!(generator.next_id(), Ok(0));
+    assert_eq!(config.decode(generator.next_id().unwrap()), Snowflake { millis: 0, worker: 0, sequence: 1 });
+}
+
+#[test]
+fn generator_rejects_invalid_input() {
+    assert!(Generator::new(CONFIG, 1024, || 0u64).is_none());
+    let mut before_epoch = Generator::new(CONFIG, 0, || TWITTER_EPOCH - 1).unwrap();
+    assert_eq!(before_epoch.next_id(), Err(SnowflakeError::BeforeEpoch));
+    let mut overflow = Generator::new(CONFIG, 0, || TWITTER_EPOCH + (1 << 41)).unwrap();
+    assert_eq!(overflow.next_id(), Err(SnowflakeError::TimestampOverflow));
+    let mut last = Generator::new(CONFIG, 1023, || TWITTER_EPOCH + (1 << 41) - 1).unwrap();
+    assert_eq!(last.next_id(), Ok(i64::MAX as u64 - 4095));
+}
+
+#[test]
+fn b62_u64_works() {
+    assert_eq!(&get_b62_from_u64(0), b"00000000000");
+    assert_eq!(&get_b62_from_u64(61), b"0000000000Z");
+    assert_eq!(&get_b62_from_u64(u64::MAX), b"lYGhA16ahyf");
+    assert_eq!(&CONFIG.get_b62(1_212_092_628_029_698_048), &get_b62_from_u64(1_212_092_628_029_698_048));
+    for value in &[0, 1, 61, 62, 1_212_092_628_029_698_048, u64::MAX] {
+        assert_eq!(get_u64(std::str::from_utf8(&get_b62_from_u64(*value)).unwrap()), Some(*value));
+    }
+}
+
+#[test]
+fn get_u64_rejects_invalid_input() {
+    let invalid = [
+        "lYGhA16ahyg",  // u64::MAX + 1
+        "zzzzzzzzzzz",  // Too large
+        "0000000000",   // Too short
+        "000000000000", // Too long
+        "0000000000+",  // Invalid char
+    ];
+    for b62 in invalid.iter() {
+        assert_eq!(get_u64(b62), None, "{}", b62);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn system_clock_works() {
+    use rb62::snowflake::SystemClock;
+
+    let mut generator = Generator::new(CONFIG, 1, SystemClock).unwrap();
+    let id = generator.next_id().unwrap();
+    assert!(CONFIG.decode(id).millis > 1_600_000_000_000);
+}
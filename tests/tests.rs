@@ -1,4 +1,4 @@
-use rb62::{get_integer, get_b62, get_hex};
+use rb62::{get_integer, get_b62, get_hex, get_b62_unpadded_from_integer, get_integer_unpadded};
 use std::str;
 
 struct Base62TestData(&'static str, &'static str);
@@ -65,8 +65,48 @@ fn rust_get_b62_should_return_none_when_input_invalid() {
     }
 }
 
+#[test]
+fn rust_get_b62_unpadded_works() {
+    let mut buf = [0u8; 22];
+    assert_eq!(get_b62_unpadded_from_integer(0, &mut buf), "0");
+    assert_eq!(get_b62_unpadded_from_integer(1, &mut buf), "1");
+    assert_eq!(get_b62_unpadded_from_integer(61, &mut buf), "Z");
+    assert_eq!(get_b62_unpadded_from_integer(62, &mut buf), "10");
+    for test in TEST_DATA {
+        let i = get_integer(test.0).unwrap();
+        let b62 = get_b62_unpadded_from_integer(i, &mut buf);
+        let trimmed = test.0.trim_start_matches('0');
+        assert_eq!(b62, if trimmed.is_empty() { "0" } else { trimmed });
+        assert_eq!(get_integer_unpadded(b62), Some(i));
+        assert_eq!(get_integer_unpadded(test.0), Some(i));
+    }
+}
+
+#[test]
+fn rust_get_integer_unpadded_works() {
+    assert_eq!(get_integer_unpadded("0"), Some(0));
+    assert_eq!(get_integer_unpadded("1"), Some(1));
+    assert_eq!(get_integer_unpadded("001"), Some(1));
+    assert_eq!(get_integer_unpadded("ZZZZZZZZZZZZZZZZZZZZZ"), Some(62u128.pow(21) - 1));
+}
+
+#[test]
+fn rust_get_integer_unpadded_should_return_none_when_input_invalid() {
+    let invalid_inputs = [
+        "",                         // Empty
+        "1+",                       // Invalid characters (+)
+        "7N42dgm5tFLK9N8MT7fHC8",   // Too large (max is 7N42dgm5tFLK9N8MT7fHC7)
+        "ZZZZZZZZZZZZZZZZZZZZZZ",   // Definately too large to fit in 128 bits
+        "00000000000000000000001",  // Too long, even with leading zeros
+    ];
+
+    for invalid in &invalid_inputs {
+        assert_eq!(get_integer_unpadded(invalid), None, "{}", invalid);
+    }
+}
+
 
-const TEST_DATA: &'static [Base62TestData] = &[
+const TEST_DATA: &[Base62TestData] = &[
     Base62TestData("0000000000000000000001", "00000000000000000000000000000001"),
     Base62TestData("0000000000000000000002", "00000000000000000000000000000002"),
     Base62TestData("0000000000000000000004", "00000000000000000000000000000004"),
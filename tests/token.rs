@@ -0,0 +1,57 @@
+use rb62::token::{TokenFormat, MAX_TOKEN_LEN};
+
+const FORMAT: TokenFormat = TokenFormat::new("rbk_");
+const ENTROPY: [u8; 16] = [
+    0xdb, 0xc3, 0xd5, 0xeb, 0xe3, 0x44, 0x48, 0x4d, 0xa3, 0xe2, 0x44, 0x87, 0x12, 0xa0, 0x22, 0x13,
+];
+
+#[test]
+fn token_generate_works() {
+    let token = FORMAT.generate(ENTROPY);
+    assert_eq!(token.body(), 0xdbc3d5ebe344484da3e2448712a02213);
+    assert_eq!(token.checksum(), 0xd1942357); // crc32 of "rbk_6GGODyP2LIdbxIfYxy5UbN"
+
+    let mut buf = [0u8; MAX_TOKEN_LEN];
+    assert_eq!(token.encode(&mut buf), "rbk_6GGODyP2LIdbxIfYxy5UbN3PXokD");
+    assert_eq!(format!("{}", token), "rbk_6GGODyP2LIdbxIfYxy5UbN3PXokD");
+    assert_eq!(FORMAT.token_len(), 32);
+}
+
+#[test]
+fn token_parse_and_verify_works() {
+    let token = FORMAT.parse("rbk_6GGODyP2LIdbxIfYxy5UbN3PXokD").expect("token can be parsed");
+    assert_eq!(token, FORMAT.generate(ENTROPY));
+    assert!(FORMAT.verify("rbk_6GGODyP2LIdbxIfYxy5UbN3PXokD"));
+}
+
+#[test]
+fn token_parse_should_return_none_when_input_invalid() {
+    let invalid_inputs = [
+        "rbk_6GGODyP2LIdbxIfYxy5UbN3PXokE",  // Wrong checksum
+        "rbk_6GGODyP2LIdbxIfYxy5UbM3PXokD",  // Body changed
+        "rbx_6GGODyP2LIdbxIfYxy5UbN3PXokD",  // Wrong prefix
+        "rbk_6GGODyP2LIdbxIfYxy5UbN3PXok",   // Too short
+        "rbk_6GGODyP2LIdbxIfYxy5UbN3PXokDD", // Too long
+        "rbk_ZZZZZZZZZZZZZZZZZZZZZZ3PXokD",  // Body does not fit in 128 bits
+        "6GGODyP2LIdbxIfYxy5UbN3PXokD",      // No prefix
+    ];
+
+    for invalid in &invalid_inputs {
+        assert_eq!(FORMAT.parse(invalid), None, "{} should not parse", invalid);
+        assert!(!FORMAT.verify(invalid));
+    }
+}
+
+#[test]
+fn token_debug_redacts_body_and_checksum() {
+    let debug = format!("{:?}", FORMAT.generate(ENTROPY));
+    assert_eq!(debug, r#"Token { prefix: "rbk_", secret: "<redacted>" }"#);
+    assert!(!debug.contains("6GGODyP2LIdbxIfYxy5UbN"));
+    assert!(!debug.contains("3PXokD"));
+}
+
+#[test]
+#[should_panic]
+fn token_format_rejects_bad_prefix() {
+    TokenFormat::new("rbk token");
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "std")]
+use rb62::transcode::{Error, InvalidLinePolicy, Representation, Stats, Transcoder};
+
+const INPUT: &[u8] = b"6GGODyP2LIdbxIfYxy5UbN\n  0000000000000000000001 \r\njunk\n\n7N42dgm5tFLK9N8MT7fHC7";
+
+fn transcode(input: &[u8], from: Representation, to: Representation, policy: InvalidLinePolicy) -> (String, Stats) {
+    let mut output = Vec::new();
+    let stats = Transcoder::new(input, from, to).policy(policy).run(&mut output).unwrap();
+    (String::from_utf8(output).unwrap(), stats)
+}
+
+#[test]
+fn transcoder_skip_works() {
+    let (output, stats) = transcode(INPUT, Representation::Base62, Representation::Hex, InvalidLinePolicy::Skip);
+    assert_eq!(
+        output,
+        "dbc3d5ebe344484da3e2448712a02213\n00000000000000000000000000000001\nffffffffffffffffffffffffffffffff\n"
+    );
+    assert_eq!(stats, Stats { lines: 5, converted: 3, skipped: 2, passed_through: 0 });
+}
+
+#[test]
+fn transcoder_passthrough_works() {
+    let (output, stats) =
+        transcode(INPUT, Representation::Base62, Representation::Decimal, InvalidLinePolicy::Passthrough);
+    assert_eq!(
+        output,
+        "292117767806510281839191090427284431379\n1\njunk\n\n340282366920938463463374607431768211455\n"
+    );
+    assert_eq!(stats, Stats { lines: 5, converted: 3, skipped: 0, passed_through: 2 });
+}
+
+#[test]
+fn transcoder_fail_works() {
+    let mut output = Vec::new();
+    let mut transcoder = Transcoder::new(INPUT, Representation::Base62, Representation::Hex);
+    match transcoder.run(&mut output) {
+        Err(Error::InvalidLine { line_number }) => assert_eq!(line_number, 3),
+        other => panic!("expected an invalid line error, got {:?}", other),
+    }
+    assert_eq!(transcoder.stats().converted, 2);
+    assert_eq!(Error::InvalidLine { line_number: 3 }.to_string(), "line 3 is not a valid id");
+}
+
+#[test]
+fn transcoder_representations_round_trip() {
+    let representations = [
+        Representation::Base62,
+        Representation::Hex,
+        Representation::UuidHex,
+        Representation::Base64Url,
+        Representation::Decimal,
+    ];
+    for from in &representations {
+        for to in &representations {
+            let (encoded, _) = transcode(INPUT, Representation::Base62, *from, InvalidLinePolicy::Skip);
+            let (transcoded, _) = transcode(encoded.as_bytes(), *from, *to, InvalidLinePolicy::Fail);
+            let (expected, _) = transcode(INPUT, Representation::Base62, *to, InvalidLinePolicy::Skip);
+            assert_eq!(transcoded, expected, "{:?} to {:?}", from, to);
+        }
+    }
+}
+
+#[test]
+fn representation_parse_rejects_invalid_input() {
+    assert_eq!(Representation::Hex.parse("+bc3d5ebe344484da3e2448712a02213"), None);
+    assert_eq!(Representation::Hex.parse("1"), None);
+    assert_eq!(Representation::Decimal.parse("+1"), None);
+    assert_eq!(Representation::Decimal.parse("340282366920938463463374607431768211456"), None);
+    assert_eq!(Representation::UuidHex.parse("DBC3D5EB-E344-484D-A3E2-448712A02213"), Some(0xdbc3d5ebe344484da3e2448712a02213));
+}
+
+#[test]
+fn transcoder_counts_non_utf8_lines_as_invalid() {
+    let (output, stats) =
+        transcode(b"\xff\xfe\n6GGODyP2LIdbxIfYxy5UbN\n", Representation::Base62, Representation::Hex, InvalidLinePolicy::Skip);
+    assert_eq!(output, "dbc3d5ebe344484da3e2448712a02213\n");
+    assert_eq!(stats.skipped, 1);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "alloc")]
+use rb62::id::{Id, Playlist, Track};
+use rb62::uri::{percent_decode, LocalFile, PercentEncode, SpotifyUri, UriError};
+
+const VALUE: u128 = 0xdbc3d5ebe344484da3e2448712a02213;
+
+#[test]
+fn spotify_uri_modern_forms_work() {
+    let uri: SpotifyUri = "spotify:track:6GGODyP2LIdbxIfYxy5UbN".parse().unwrap();
+    assert_eq!(uri, SpotifyUri::Track(Id::<Track>::new(VALUE)));
+    assert_eq!(uri.to_string(), "spotify:track:6GGODyP2LIdbxIfYxy5UbN");
+
+    for kind in &["album", "artist", "playlist", "show", "episode"] {
+        let text = format!("spotify:{}:6GGODyP2LIdbxIfYxy5UbN", kind);
+        assert_eq!(text.parse::<SpotifyUri>().unwrap().to_string(), text);
+    }
+}
+
+#[test]
+fn spotify_uri_user_playlist_works() {
+    let uri: SpotifyUri = "spotify:user:j%C3%B6rg+b:playlist:6GGODyP2LIdbxIfYxy5UbN".parse().unwrap();
+    assert_eq!(uri, SpotifyUri::UserPlaylist { user: "jörg b".to_owned(), playlist: Id::new(VALUE) });
+    assert_eq!(uri.user(), Some("jörg b"));
+    assert_eq!(uri.to_string(), "spotify:user:j%C3%B6rg+b:playlist:6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(uri.normalize(), SpotifyUri::Playlist(Id::<Playlist>::new(VALUE)));
+}
+
+#[test]
+fn spotify_uri_user_collection_works() {
+    let uri: SpotifyUri = "spotify:user:spotify%3Aeng:collection".parse().unwrap();
+    assert_eq!(uri, SpotifyUri::UserCollection { user: "spotify:eng".to_owned() });
+    assert_eq!(uri.to_string(), "spotify:user:spotify%3Aeng:collection");
+    assert_eq!(uri.clone().normalize(), uri);
+}
+
+#[test]
+fn spotify_uri_local_file_works() {
+    let uri: SpotifyUri = "spotify:local:Daft+Punk:Discovery:One+More+Time%21:320".parse().unwrap();
+    let local = LocalFile {
+        artist: "Daft Punk".to_owned(),
+        album: "Discovery".to_owned(),
+        title: "One More Time!".to_owned(),
+        duration_secs: 320,
+    };
+    assert_eq!(uri, SpotifyUri::Local(local));
+    assert_eq!(uri.to_string(), "spotify:local:Daft+Punk:Discovery:One+More+Time%21:320");
+
+    let uri: SpotifyUri = "spotify:local:::untitled:0".parse().unwrap();
+    assert_eq!(uri.to_string(), "spotify:local:::untitled:0");
+}
+
+#[test]
+fn spotify_uri_should_return_specific_errors() {
+    let invalid_inputs = [
+        ("spotify.track:6GGODyP2LIdbxIfYxy5UbN", UriError::NotSpotify),
+        ("spotify:song:6GGODyP2LIdbxIfYxy5UbN", UriError::UnknownForm),
+        ("spotify:track:6GGODyP2LIdbxIfYxy5UbN:extra", UriError::UnknownForm),
+        ("spotify:user:bob:playlist", UriError::UnknownForm),
+        ("spotify:track:6GGODyP2LIdbxIfYxy5Ub", UriError::InvalidId),
+        ("spotify:user:bob:playlist:7N42dgm5tFLK9N8MT7fHC8", UriError::InvalidId),
+        ("spotify:user:bob%2:collection", UriError::InvalidEncoding),
+        ("spotify:user:%FF:collection", UriError::InvalidEncoding),  // Not UTF-8
+        ("spotify:local:a:b:c:-1", UriError::InvalidDuration),
+        ("spotify:local:a:b:c:", UriError::InvalidDuration),
+        ("spotify:local:a:b:c:99999999999", UriError::InvalidDuration),
+    ];
+    for (invalid, error) in &invalid_inputs {
+        assert_eq!(invalid.parse::<SpotifyUri>(), Err(*error), "{} should not parse", invalid);
+    }
+}
+
+#[test]
+fn percent_encoding_round_trips() {
+    for text in &["plain", "with space", "a:b+c%d", "åäö ✓", ""] {
+        let encoded = PercentEncode(text).to_string();
+        assert!(!encoded.contains(':') && !encoded.contains(' '));
+        assert_eq!(percent_decode(&encoded).as_deref(), Some(*text));
+    }
+    assert_eq!(percent_decode("%e2%9c%93"), Some("✓".to_owned()));
+}
@@ -0,0 +1,49 @@
+use rb62::{get_b62_from_uuid_hex, get_integer_from_uuid_hex, get_uuid_hex};
+use std::str;
+
+#[test]
+fn rust_get_uuid_hex_works() {
+    let uuid_hex = get_uuid_hex("6GGODyP2LIdbxIfYxy5UbN").expect("get_uuid_hex can parse b62");
+    assert_eq!(str::from_utf8(&uuid_hex).unwrap(), "dbc3d5eb-e344-484d-a3e2-448712a02213");
+
+    let uuid_hex = get_uuid_hex("0000000000000000000000").unwrap();
+    assert_eq!(str::from_utf8(&uuid_hex).unwrap(), "00000000-0000-0000-0000-000000000000");
+}
+
+#[test]
+fn rust_get_b62_from_uuid_hex_works() {
+    for uuid_hex in &["dbc3d5eb-e344-484d-a3e2-448712a02213", "DBC3D5EB-E344-484D-A3E2-448712A02213"] {
+        let b62 = get_b62_from_uuid_hex(uuid_hex).expect("get_b62_from_uuid_hex can parse uuid");
+        assert_eq!(str::from_utf8(&b62).unwrap(), "6GGODyP2LIdbxIfYxy5UbN");
+    }
+    assert_eq!(get_integer_from_uuid_hex("ffffffff-ffff-ffff-ffff-ffffffffffff"), Some(u128::MAX));
+}
+
+#[test]
+fn rust_get_integer_from_uuid_hex_should_return_none_when_input_invalid() {
+    let invalid_inputs = [
+        "dbc3d5ebe344484da3e2448712a02213",      // No dashes
+        "dbc3d5eb-e344-484d-a3e2-448712a0221",   // Too short
+        "dbc3d5eb-e344-484d-a3e2-448712a022133", // Too long
+        "dbc3d5ebe-344-484d-a3e2-448712a02213",  // Dash in the wrong place
+        "dbc3d5eb-e344-484d-a3e2-448712a0221g",  // Invalid characters (g)
+        "+bc3d5eb-e344-484d-a3e2-448712a02213",  // Sign is not a hex digit
+    ];
+
+    for invalid in &invalid_inputs {
+        assert_eq!(get_integer_from_uuid_hex(invalid), None, "{} should not parse", invalid);
+        assert_eq!(get_b62_from_uuid_hex(invalid), None);
+    }
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn rust_uuid_feature_works() {
+    use rb62::{get_b62_from_uuid, get_integer, get_uuid};
+
+    let uuid = get_uuid("6GGODyP2LIdbxIfYxy5UbN").expect("get_uuid can parse b62");
+    assert_eq!(uuid.to_string(), "dbc3d5eb-e344-484d-a3e2-448712a02213");
+    assert_eq!(uuid.as_u128(), get_integer("6GGODyP2LIdbxIfYxy5UbN").unwrap());
+    assert_eq!(&get_b62_from_uuid(&uuid), b"6GGODyP2LIdbxIfYxy5UbN");
+    assert_eq!(get_uuid("6GGODyP2LIdbxIfYxy5Ub"), None);
+}